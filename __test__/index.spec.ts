@@ -1,5 +1,5 @@
 import { expect, test } from 'vitest'
-import { optimize } from '../index'
+import { builtinPlugins, optimize } from '../index'
 
 test('sync function from native code', () => {
   const inputXml = `
@@ -29,8 +29,356 @@ test('sync function from native code', () => {
 </svg>
 `
 
-  const res = optimize(inputXml)
+  const res = optimize(inputXml).data
   // biome-ignore lint/suspicious/noConsole: <explanation>
   console.log(res)
   expect(1).toBe(1)
 })
+
+test('removeDimensions replaces width/height with viewBox', () => {
+  const res = optimize('<svg width="100px" height="50"><path d="M0 0"/></svg>', { removeDimensions: true }).data
+  expect(res).toBe('<svg viewBox="0 0 100 50"><path d="M0 0"/></svg>')
+})
+
+test('removeViewBox drops a viewBox matching width/height', () => {
+  const res = optimize('<svg width="100" height="50" viewBox="0 0 100 50"/>', { removeViewBox: true }).data
+  expect(res).toBe('<svg width="100" height="50"/>')
+})
+
+test('prefixIds prefixes ids, classes and references', () => {
+  const inputXml = '<svg><style>.a{fill:url(#b)}</style><path id="b" class="a"/><use href="#b"/></svg>'
+  expect(optimize(inputXml, { path: 'icons/star.svg', prefixIds: true }).data).toBe(
+    '<svg><style>.star_svg__a{fill:url(#star_svg__b)}</style><path id="star_svg__b" class="star_svg__a"/><use href="#star_svg__b"/></svg>',
+  )
+  expect(optimize(inputXml, { prefixIds: { prefix: () => 'icon', prefixClassNames: false } }).data).toBe(
+    '<svg><style>.a{fill:url(#icon__b)}</style><path id="icon__b" class="a"/><use href="#icon__b"/></svg>',
+  )
+})
+
+test('sortAttrs orders attributes like svgo', () => {
+  const res = optimize('<svg xmlns="http://www.w3.org/2000/svg"><rect fill="red" height="10" width="10" id="a"/></svg>', {
+    sortAttrs: true,
+  }).data
+  expect(res).toBe('<svg xmlns="http://www.w3.org/2000/svg"><rect id="a" width="10" height="10" fill="red"/></svg>')
+})
+
+test('removeAttrs keeps currentColor when asked to', () => {
+  const res = optimize('<svg><path data-name="a" fill="currentColor" stroke="red"/></svg>', {
+    removeAttrs: { attrs: ['data-.*', '(fill|stroke)'], preserveCurrentColor: true },
+  }).data
+  expect(res).toBe('<svg><path fill="currentColor"/></svg>')
+})
+
+test('addAttributesToSVGElement and addClassesToSVGElement decorate the root svg', () => {
+  const res = optimize('<svg class="icon"><path d="M0 0"/></svg>', {
+    addAttributesToSVGElement: { attributes: [{ 'aria-hidden': 'true' }, { focusable: 'false' }] },
+    addClassesToSVGElement: { classNames: ['icon', 'icon-star'] },
+  }).data
+  expect(res).toBe('<svg class="icon icon-star" aria-hidden="true" focusable="false"><path d="M0 0"/></svg>')
+})
+
+test('removeXlink migrates xlink:href to href', () => {
+  const inputXml =
+    '<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"><use xlink:href="#a"/><filter xlink:href="#f"/></svg>'
+  expect(optimize(inputXml, { removeXlink: true }).data).toBe(
+    '<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"><use href="#a"/><filter xlink:href="#f"/></svg>',
+  )
+  expect(optimize(inputXml, { removeXlink: { includeLegacy: true } }).data).toBe(
+    '<svg xmlns="http://www.w3.org/2000/svg"><use href="#a"/><filter href="#f"/></svg>',
+  )
+  const plugins = [{ name: 'removeXlink', params: { includeLegacy: true } }]
+  expect(optimize(inputXml, { plugins }).data).toBe(
+    '<svg xmlns="http://www.w3.org/2000/svg"><use href="#a"/><filter href="#f"/></svg>',
+  )
+})
+
+test('removeUnusedNS drops namespace declarations nobody uses', () => {
+  const inputXml =
+    '<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:foo="http://foo"><foo:bar/></svg>'
+  expect(optimize(inputXml, { removeUnusedNS: true }).data).toBe(
+    '<svg xmlns="http://www.w3.org/2000/svg" xmlns:foo="http://foo"><foo:bar/></svg>',
+  )
+  expect(optimize(inputXml, { removeUnusedNS: true, removeXMLNS: true }).data).toBe(
+    '<svg xmlns:foo="http://foo"><foo:bar/></svg>',
+  )
+})
+
+test('removeUselessStrokeAndFill respects inherited stroke', () => {
+  const res = optimize('<svg><g stroke="red"><path d="M0 0" stroke="none" stroke-width="2"/></g><path d="M1 1" stroke-width="2"/></svg>', {
+    removeUselessStrokeAndFill: true,
+  }).data
+  expect(res).toBe('<svg><g stroke="red"><path d="M0 0" stroke="none"/></g><path d="M1 1"/></svg>')
+})
+
+test('removeOffCanvasPaths removes paths outside the viewBox', () => {
+  const res = optimize(
+    '<svg viewBox="0 0 100 100"><path d="M10 10h10v10z"/><path d="M-10 -10q60 -60 0 -30"/><g transform="translate(-300)"><path d="M310 10h10"/></g></svg>',
+    { removeOffCanvasPaths: true },
+  ).data
+  expect(res).toBe('<svg viewBox="0 0 100 100"><path d="M10 10h10v10z"/><g transform="translate(-300)"><path d="M310 10h10"/></g></svg>')
+})
+
+test('removeScripts unwraps javascript: links and drops event handlers', () => {
+  const res = optimize(
+    '<svg onload="init()"><script>alert(1)</script><a href="javascript:alert(1)"><path d="M0 0"/></a><image href="photo.jpg"/><style>path{}</style></svg>',
+    { removeScripts: true, removeRasterImages: true, removeStyleElement: true },
+  ).data
+  expect(res).toBe('<svg><path d="M0 0"/></svg>')
+})
+
+test('content of unwrapped links is visited by every plugin', () => {
+  const res = optimize(
+    '<svg><a href="javascript:x()"><a href="javascript:y()"><script/><path onclick="y()" d="M0 0"/></a><desc>d</desc></a></svg>',
+    { removeScripts: true },
+  ).data
+  expect(res).toBe('<svg><path d="M0 0"/></svg>')
+})
+
+test('convertOneStopGradients and mergeGradients simplify gradient definitions', () => {
+  const oneStop = optimize(
+    '<svg><defs><linearGradient id="a"><stop stop-color="#ddc4cc"/></linearGradient></defs><rect fill="url(#a)" style="stroke:url(#a)"/></svg>',
+    { convertOneStopGradients: true },
+  ).data
+  expect(oneStop).toBe('<svg><rect fill="#ddc4cc" style="stroke:#ddc4cc"/></svg>')
+
+  const merged = optimize(
+    '<svg><linearGradient id="a"><stop offset="0"/><stop offset="1" stop-color="red"/></linearGradient><linearGradient id="b"><stop offset="0"/><stop offset="1" stop-color="red"/></linearGradient><rect fill="url(#b)"/></svg>',
+    { mergeGradients: true },
+  ).data
+  expect(merged).toBe(
+    '<svg><linearGradient id="a"><stop offset="0"/><stop offset="1" stop-color="red"/></linearGradient><rect fill="url(#a)"/></svg>',
+  )
+
+  const sharedId =
+    '<svg><linearGradient id="a"><stop offset="0"/></linearGradient><linearGradient id="a"><stop offset="0"/></linearGradient><rect fill="url(#a)"/></svg>'
+  expect(optimize(sharedId, { mergeGradients: true }).data).toBe(sharedId)
+})
+
+test('reusePaths keeps paths with children and ids referenced through quoted urls', () => {
+  const plugins = ['reusePaths']
+  const d = 'M10 10h80v80H10zM20 20h60v60H20zM30 30h40v40H30z'
+  const path = `<path d="${d}"/>`
+  const svg = (content) => `<svg xmlns:xlink="http://www.w3.org/1999/xlink">${content}</svg>`
+
+  // <title> and <animate> would be lost on a <use>
+  const withChildren = svg(`<path d="${d}"><title>a</title></path>${path}${path}${path}<path d="${d}"><animate/></path>`)
+  expect(optimize(withChildren, { plugins }).data).toBe(
+    svg(
+      `<defs><path d="${d}" id="reuse-0"/></defs><path d="${d}"><title>a</title></path>` +
+        '<use xlink:href="#reuse-0"/><use xlink:href="#reuse-0"/><use xlink:href="#reuse-0"/>' +
+        `<path d="${d}"><animate/></path>`,
+    ),
+  )
+
+  // an unreferenced id moves into <defs>, a referenced one stays on the <use>
+  expect(optimize(svg(`<path id="p" d="${d}"/>${path}${path}`), { plugins }).data).toContain(
+    `<defs><path d="${d}" id="p"/></defs>`,
+  )
+  for (const reference of [`style="fill:url('#p')"`, `fill='url("#p")'`, 'fill="url( #p )"']) {
+    const res = optimize(svg(`<path id="p" d="${d}"/>${path}${path}<rect ${reference}/>`), { plugins }).data
+    expect(res).toContain('<use id="p" xlink:href="#reuse-0"/>')
+  }
+})
+
+test('reusePaths does not move ids that style rules select', () => {
+  const plugins = ['reusePaths']
+  const d = 'M10 10h80v80H10zM20 20h60v60H20zM30 30h40v40H30z'
+  const paths = `<path id="a" d="${d}"/><path d="${d}"/><path d="${d}"/>`
+  // #a{fill:red} styles one path and must not start applying to every copy
+  const input = `<svg><style>#a{fill:red}</style>${paths}</svg>`
+  expect(optimize(input, { plugins }).data).toBe(input)
+  const xlink = `<svg xmlns:xlink="http://www.w3.org/1999/xlink"><style>#a{fill:red}</style>${paths}</svg>`
+  expect(optimize(xlink, { plugins }).data).toContain(
+    '<style>#a{fill:red}</style><use id="a" xlink:href="#reuse-0"/><use xlink:href="#reuse-0"/>',
+  )
+})
+
+test('moveGroupAttrsToElems and removeNonInheritableGroupAttrs', () => {
+  const res = optimize(
+    '<svg><g transform="scale(2)" stop-color="red"><path transform="rotate(45)" d="M0 0"/><text/></g></svg>',
+    { moveGroupAttrsToElems: true, removeNonInheritableGroupAttrs: true },
+  ).data
+  expect(res).toBe('<svg><g><path transform="scale(2) rotate(45)" d="M0 0"/><text transform="scale(2)"/></g></svg>')
+})
+
+test('moveElemsAttrsToGroup handles conflicting and partially shared attributes like svgo', () => {
+  const plugins = ['moveElemsAttrsToGroup']
+  // the children's common value overrides the group's own value
+  expect(optimize('<svg><g fill="red"><path fill="blue"/><path fill="blue"/></g></svg>', { plugins }).data).toBe(
+    '<svg><g fill="blue"><path/><path/></g></svg>',
+  )
+  // several attributes that are not shared by every child
+  expect(optimize('<svg><g><path fill="red" stroke="red" color="red"/><path/></g></svg>', { plugins }).data).toBe(
+    '<svg><g><path fill="red" stroke="red" color="red"/><path/></g></svg>',
+  )
+  // transforms stay on paths so that convertPathData can apply them
+  const paths = '<svg><g><path transform="scale(2)"/><path transform="scale(2)"/></g></svg>'
+  expect(optimize(paths, { plugins }).data).toBe(paths)
+})
+
+test('cleanupAttrs and cleanupEnableBackground tidy Illustrator exports', () => {
+  const res = optimize('<svg width="100" height="50" enable-background="new 0 0 100 50" class="  a\n b   c "/>', {
+    cleanupAttrs: true,
+    cleanupEnableBackground: true,
+  }).data
+  expect(res).toBe('<svg width="100" height="50" class="a b c"/>')
+})
+
+test('convertPathData applies transforms to path coordinates', () => {
+  const res = optimize(
+    '<svg><path transform="translate(10 20)" d="M0 0h10v10z"/><path transform="scale(2)" stroke="red" d="M0 0l5 5"/><path transform="scale(2 1)" stroke="red" d="M0 0l5 5"/></svg>',
+    { convertPathData: true },
+  ).data
+  expect(res).toBe(
+    '<svg><path d="M10 20h10v10z"/><path stroke="red" d="M0 0l10 10" stroke-width="2"/><path transform="scale(2 1)" stroke="red" d="M0 0l5 5"/></svg>',
+  )
+})
+
+test('convertPathData leaves paths it cannot fully parse untouched', () => {
+  const plugins = ['convertPathData']
+  for (const d of ['garbage', 'M0 0L10 10 garbage', 'M0 0L10', 'M0 0zL10']) {
+    const input = `<svg><path transform="translate(1)" d="${d}"/></svg>`
+    expect(optimize(input, { plugins }).data).toBe(input)
+  }
+})
+
+test('plugins accepts preset-default with overrides', () => {
+  const input =
+    '<svg xmlns="http://www.w3.org/2000/svg" width="10" height="10" viewBox="0 0 10 10"><title>t</title><!-- c --><g><path d="M0 0" fill="blue"/><path d="M1 1" fill="blue"/></g></svg>'
+  const res = optimize(input, {
+    plugins: [{ name: 'preset-default', params: { overrides: { removeTitle: false, removeViewBox: false } } }, 'removeDimensions'],
+  }).data
+  expect(res).toBe(
+    '<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10"><title>t</title><g fill="blue"><path d="M0 0"/><path d="M1 1"/></g></svg>',
+  )
+  expect(() => optimize(input, { plugins: ['unknownPlugin'] })).toThrow('unknown plugin "unknownPlugin"')
+  expect(() => optimize(input, { plugins: [{ name: 'removeDesc', params: { removeAnyy: true } }] })).toThrow(
+    'removeDesc: unknown param `removeAnyy`, did you mean "removeAny"?',
+  )
+  expect(() => optimize(input, { plugins: ['removeViewBo'] })).toThrow('did you mean "removeViewBox"?')
+  expect(builtinPlugins().find((plugin) => plugin.name === 'removeDesc')?.presetDefault).toBe(true)
+})
+
+test('plugins that are not implemented yet are skipped and reported', () => {
+  const res = optimize('<svg><!--c--></svg>', {
+    plugins: [
+      'mergePaths',
+      'removeComments',
+      { name: 'preset-default', params: { overrides: { cleanupIds: false, convertColors: {} } } },
+    ],
+  })
+  expect(res.data).toBe('<svg/>')
+  expect(res.skippedPlugins).toEqual(['mergePaths', 'convertColors'])
+  expect(optimize('<svg/>').skippedPlugins).toEqual([])
+})
+
+test('stats reports what each plugin removed', () => {
+  const input = '<svg><metadata><a/></metadata><title>t</title><path d="M0 0"/></svg>'
+  const res = optimize(input, { stats: true })
+  expect(res.data).toBe('<svg><path d="M0 0"/></svg>')
+  expect(res.originalSize).toBe(input.length)
+  expect(res.optimizedSize).toBe(res.data.length)
+  expect(res.stats?.find((plugin) => plugin.name === 'removeMetadata')?.removedNodes).toBe(2)
+  expect(optimize(input).stats).toBeUndefined()
+})
+
+test('custom plugins can edit and remove elements', () => {
+  const res = optimize('<svg><g id="star"><path d="M0 0"/><rect data-draft="1"/></g></svg>', {
+    plugins: [
+      'removeDesc',
+      {
+        name: 'designTokens',
+        element: {
+          enter: (el) => {
+            if (el.hasAttribute('data-draft')) el.remove()
+            if (el.name === 'g') el.setAttribute('data-icon', el.getAttribute('id') ?? '')
+          },
+        },
+        root: {
+          exit: (root) => root.children[0].setAttribute('role', 'img'),
+        },
+      },
+    ],
+  })
+  expect(res.data).toBe('<svg role="img"><g id="star" data-icon="star"><path d="M0 0"/></g></svg>')
+  expect(() =>
+    optimize('<svg/>', {
+      plugins: [{ name: 'broken', element: { enter: () => { throw new Error('oops') } } }],
+    }),
+  ).toThrow('broken')
+})
+
+test('custom plugin views can be used together with their parent views', () => {
+  let kept
+  const res = optimize('<svg><g><a/><b/></g><!--c--><g><c/></g></svg>', {
+    plugins: [
+      {
+        name: 'nested',
+        root: {
+          exit: (root) => {
+            const svg = root.children[0]
+            const [first, second] = svg.children
+            const [a, b] = first.children
+            svg.setAttribute('id', 'icon')
+            b.remove()
+            first.children[0].setAttribute('x', '1')
+            second.children[0].remove()
+            second.remove()
+            a.setAttribute('y', '2')
+            kept = a
+          },
+        },
+      },
+    ],
+  })
+  expect(res.data).toBe('<svg id="icon"><g><a x="1" y="2"/></g><!--c--></svg>')
+  expect(() => kept.name).toThrow('inside the plugin callback')
+})
+
+test('sequential traversal runs each plugin over the whole document like svgo', () => {
+  const input = '<svg><text><!--c--></text><path d="M0 0"/></svg>'
+  const plugins = ['removeComments', 'removeEmptyText']
+  expect(optimize(input, { plugins }).data).toBe('<svg><text/><path d="M0 0"/></svg>')
+  expect(optimize(input, { plugins, traversal: 'sequential' }).data).toBe('<svg><path d="M0 0"/></svg>')
+})
+
+test('datauri encodes the optimized svg like svgo', () => {
+  const input = '<svg xmlns="http://www.w3.org/2000/svg"><!--c--><path d="M0 0" fill="#f00"/></svg>'
+  const svg = '<svg xmlns="http://www.w3.org/2000/svg"><path d="M0 0" fill="#f00"/></svg>'
+  expect(optimize(input, { datauri: 'base64' }).data).toBe(
+    `data:image/svg+xml;base64,${Buffer.from(svg).toString('base64')}`,
+  )
+  expect(optimize(input, { datauri: 'enc' }).data).toBe(
+    'data:image/svg+xml,%3Csvg xmlns="http://www.w3.org/2000/svg"%3E%3Cpath d="M0 0" fill="%23f00"/%3E%3C/svg%3E',
+  )
+  expect(optimize(input, { datauri: 'unenc' }).data).toBe(`data:image/svg+xml,${svg}`)
+  expect(() => optimize(input, { datauri: 'utf8' })).toThrow('unknown datauri "utf8"')
+})
+
+test('sortDefsChildren orders defs by frequency, then name length, then name', () => {
+  const res = optimize(
+    '<svg><defs><path id="1"/><linearGradient id="g"/><rect/><path id="2"/><circle/><!--c--><mask/></defs></svg>',
+    { plugins: ['sortDefsChildren'] },
+  ).data
+  // rect and mask tie on length and are ordered by descending name; the comment keeps its slot
+  expect(res).toBe(
+    '<svg><defs><path id="1"/><path id="2"/><linearGradient id="g"/><circle/><rect/><!--c--><mask/></defs></svg>',
+  )
+})
+
+test('removeElementsByAttr matches whole ids and any of the class names', () => {
+  const res = optimize(
+    '<svg><rect id="a"/><rect id="ab"/><g class="foo x"><circle/></g><rect class="y"/><rect class="xy"/></svg>',
+    { plugins: [{ name: 'removeElementsByAttr', params: { id: 'a', class: ['x', 'y'] } }] },
+  ).data
+  expect(res).toBe('<svg><rect id="ab"/><rect class="xy"/></svg>')
+})
+
+test('removeEmptyAttrs keeps empty conditional processing attributes', () => {
+  const res = optimize(
+    '<svg><g fill="" stroke=" " id="" requiredFeatures="" requiredExtensions="" systemLanguage=""/></svg>',
+    { plugins: ['removeEmptyAttrs'] },
+  ).data
+  // an empty systemLanguage and friends make the element never render, so removing them would show it
+  expect(res).toBe('<svg><g stroke=" " requiredFeatures="" requiredExtensions="" systemLanguage=""/></svg>')
+})
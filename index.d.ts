@@ -1,3 +1,251 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
-export declare function optimize(inputXml: string): string
+/** 传给 `element.enter`/`element.exit` 回调的元素 */
+export declare class XastElement {
+  /** 标签名 */
+  get name(): string
+  set name(name: string)
+  /** 所有属性的副本，修改它不会影响元素 */
+  get attributes(): Record<string, string>
+  getAttribute(name: string): string | null
+  hasAttribute(name: string): boolean
+  /** 已存在时修改属性值，否则添加到末尾 */
+  setAttribute(name: string, value: string): void
+  removeAttribute(name: string): void
+  /** 子元素，不包括文本、注释等其他节点 */
+  get children(): Array<XastElement>
+  /** 所有子孙文本节点的内容 */
+  get textContent(): string
+  /** 在回调返回后删除该元素及其子孙节点 */
+  remove(): void
+}
+
+/** 传给 `root.enter`/`root.exit` 回调的文档根节点 */
+export declare class XastRoot {
+  /** 顶层元素，通常只有根 `<svg>` */
+  get children(): Array<XastElement>
+}
+
+/**
+ * 列出所有可以在 `plugins` 中使用的内置插件。
+ * svgo 中尚未实现的插件不在列表中，它们出现在 `plugins` 中时会被直接跳过
+ */
+export declare function builtinPlugins(): Array<BuiltinPlugin>
+
+/** 内置插件的信息 */
+export interface BuiltinPlugin {
+  name: string
+  description: string
+  /** 是否包含在 `preset-default` 中 */
+  presetDefault: boolean
+}
+
+export declare function optimize(inputXml: string, config?: OptimizeConfig | undefined | null): OptimizeOutput
+
+export interface OptimizeConfig {
+  /** 给根 `<svg>` 添加属性，已存在的属性不会被覆盖 */
+  addAttributesToSVGElement?: AddAttributesToSVGElementConfig
+  /** 给根 `<svg>` 添加 class，与已有的 class 合并 */
+  addClassesToSVGElement?: AddClassesToSVGElementConfig
+  /** 清理属性值中的换行和多余空白，默认关闭 */
+  cleanupAttrs?: boolean | CleanupAttrsConfig
+  /** 删除用不到的 `enable-background`，默认关闭 */
+  cleanupEnableBackground?: boolean
+  /** 把只有一个 `<stop>` 的渐变替换为纯色，默认关闭 */
+  convertOneStopGradients?: boolean
+  /** 优化路径数据，目前会把 `<path>` 的 `transform` 应用到坐标上，默认关闭 */
+  convertPathData?: boolean | ConvertPathDataConfig
+  /** 合并属性和 `<stop>` 完全相同的渐变，默认关闭 */
+  mergeGradients?: boolean
+  /** 把 `<g>` 的 `transform` 移到子元素上，默认关闭 */
+  moveGroupAttrsToElems?: boolean
+  /** 被优化文件的路径，`prefixIds` 用它生成默认前缀 */
+  path?: string
+  /** svgo 格式的插件列表，可以使用 `preset-default`，设置后忽略其他插件开关 */
+  plugins?: Array<string | PluginConfigObject>
+  /** 给 ID 和 class 加前缀，默认关闭 */
+  prefixIds?: boolean | PrefixIdsConfig
+  /** 按 `element:attribute:value` 规则删除属性 */
+  removeAttrs?: RemoveAttrsConfig
+  /** 删除指定 `id` 或 `class` 的元素 */
+  removeElementsByAttr?: RemoveElementsByAttrConfig
+  /** 删除值为空的属性，默认关闭 */
+  removeEmptyAttrs?: boolean
+  /** 删除空的 `<text>`、`<tspan>` 和 `<tref>`，默认关闭 */
+  removeEmptyText?: boolean | RemoveEmptyTextConfig
+  /** 删除 `<g>` 上不会被继承、也不作用于分组本身的表现属性，默认关闭 */
+  removeNonInheritableGroupAttrs?: boolean
+  /** 删除完全位于 `viewBox` 之外的 `<path>`，默认关闭 */
+  removeOffCanvasPaths?: boolean
+  /** 删除 JPEG、PNG、GIF 位图（包括 data URI），默认关闭 */
+  removeRasterImages?: boolean
+  /** 删除 `<script>`、`on*` 事件属性和 `javascript:` 链接，默认关闭 */
+  removeScripts?: boolean
+  /** 删除 `<style>` 元素，默认关闭 */
+  removeStyleElement?: boolean
+  /** 删除不可见的 `stroke`/`fill` 相关属性，默认关闭 */
+  removeUselessStrokeAndFill?: boolean | RemoveUselessStrokeAndFillConfig
+  /** 移除根 `<svg>` 上没有被使用的 `xmlns:*` 声明，默认关闭 */
+  removeUnusedNS?: boolean
+  /** 移除 `<svg>` 的 `xmlns` 属性，用于内联到 HTML 中，默认关闭 */
+  removeXMLNS?: boolean
+  /** 移除根 `<svg>` 的 `width`/`height`，缺少 `viewBox` 时根据尺寸补上 */
+  removeDimensions?: boolean
+  /** `viewBox` 与 `width`/`height` 一致时移除 `viewBox`，与 `removeDimensions` 同时开启时不生效 */
+  removeViewBox?: boolean
+  /** 把 `xlink:href` 等属性改写为 SVG 2 的等价形式并移除 xlink 命名空间，默认关闭 */
+  removeXlink?: boolean | RemoveXlinkConfig
+  /** 把重复的 `<path>` 移到 `<defs>` 中并用 `<use>` 引用，默认关闭 */
+  reusePaths?: boolean
+  /** 按固定顺序排列属性以提高 gzip 压缩率，默认关闭 */
+  sortAttrs?: boolean | SortAttrsConfig
+  /** 按出现频率排列 `<defs>` 的子元素，默认关闭 */
+  sortDefsChildren?: boolean
+  /** 把结果输出为 data URI：`base64`、`enc`（只做必要的转义）或 `unenc` */
+  datauri?: string
+  /** 在结果中返回每个插件删除、修改的节点数和耗时，会让优化变慢，默认关闭 */
+  stats?: boolean
+  /**
+   * `interleaved`（默认）所有插件共用一次遍历；`sequential` 与 svgo 相同，
+   * 每个插件在前一个插件处理完整棵树后再遍历，只读写当前节点的相邻插件会合并遍历
+   */
+  traversal?: string
+}
+
+export interface PrefixIdsConfig {
+  /** 前缀与原名之间的分隔符，默认 `__` */
+  delim?: string
+  /** `false` 表示不加前缀；函数形式会对每个元素调用一次 */
+  prefix?: boolean | string | ((arg: PrefixIdsInfo) => string)
+  /** 是否给 `id` 及其引用加前缀，默认 `true` */
+  prefixIds?: boolean
+  /** 是否给 `class` 加前缀，默认 `true` */
+  prefixClassNames?: boolean
+}
+
+/** 传给 `prefixIds.prefix` 回调的参数 */
+export interface PrefixIdsInfo {
+  /** 当前元素的标签名 */
+  name: string
+  path?: string
+}
+
+export interface OptimizeOutput {
+  /** 优化后的 SVG，设置了 `datauri` 时是编码后的 data URI */
+  data: string
+  /** 每个插件的统计信息，按插件执行顺序排列，只有开启 `stats` 时才有 */
+  stats?: Array<OptimizePluginStats>
+  /** 输入的字节数 */
+  originalSize: number
+  /** 输出的字节数 */
+  optimizedSize: number
+  /** 配置中请求了、但尚未实现而被跳过的 svgo 插件 */
+  skippedPlugins: Array<string>
+}
+
+export interface OptimizePluginStats {
+  name: string
+  /** 被删除的节点数，包括被删除节点的所有子孙节点 */
+  removedNodes: number
+  /** 名字、属性、内容或子节点数量发生变化的节点数 */
+  modifiedNodes: number
+  /** 插件所有钩子的耗时之和，单位微秒 */
+  elapsedMicros: number
+}
+
+/**
+ * 插件列表中的对象形式，与 svgo 的 `{ name, params }` 相同。
+ * 带有 `element` 或 `root` 回调时是自定义插件，此时忽略 `params`
+ */
+export interface PluginConfigObject {
+  name: string
+  params?: any
+  /** 进入、离开每个元素时调用，在 `enter` 中调用 `remove()` 可以删除当前元素 */
+  element?: CustomElementVisitor
+  /** 遍历开始前、结束后各调用一次 */
+  root?: CustomRootVisitor
+}
+
+export interface CustomElementVisitor {
+  enter?: ((arg: XastElement) => void)
+  exit?: ((arg: XastElement) => void)
+}
+
+export interface CustomRootVisitor {
+  enter?: ((arg: XastRoot) => void)
+  exit?: ((arg: XastRoot) => void)
+}
+
+export interface AddAttributesToSVGElementConfig {
+  /** 单个属性：只有名字的字符串，或 `{ name: value }` 对象 */
+  attribute?: string | Record<string, string>
+  /** 多个属性，每一项的格式与 `attribute` 相同 */
+  attributes?: Array<string | Record<string, string>>
+}
+
+export interface AddClassesToSVGElementConfig {
+  className?: string
+  classNames?: Array<string>
+}
+
+export interface CleanupAttrsConfig {
+  /** 删除换行，两侧都不是空白时替换为空格，默认 `true` */
+  newlines?: boolean
+  /** 去掉首尾空白，默认 `true` */
+  trim?: boolean
+  /** 把连续的空白合并为一个空格，默认 `true` */
+  spaces?: boolean
+}
+
+export interface ConvertPathDataConfig {
+  /** 把 `transform` 应用到路径坐标上，默认 `true` */
+  applyTransforms?: boolean
+  /** 有描边的路径也应用 `transform`，默认 `true` */
+  applyTransformsStroked?: boolean
+  /** 输出坐标的小数位数，默认 `3` */
+  floatPrecision?: number
+}
+
+export interface RemoveAttrsConfig {
+  /** 一个或多个 `element:attribute:value` 规则，每段都是正则表达式 */
+  attrs: string | Array<string>
+  /** 规则中的分隔符，默认 `:` */
+  elemSeparator?: string
+  /** 保留值为 `currentColor` 的 `fill` 和 `stroke`，默认 `false` */
+  preserveCurrentColor?: boolean
+}
+
+export interface RemoveElementsByAttrConfig {
+  id?: string | Array<string>
+  class?: string | Array<string>
+}
+
+export interface RemoveEmptyTextConfig {
+  /** 删除没有子节点的 `<text>`，默认 `true` */
+  text?: boolean
+  /** 删除没有子节点的 `<tspan>`，默认 `true` */
+  tspan?: boolean
+  /** 删除没有 `xlink:href` 的 `<tref>`，默认 `true` */
+  tref?: boolean
+}
+
+export interface RemoveUselessStrokeAndFillConfig {
+  /** 删除无用的 `stroke*` 属性，默认 `true` */
+  stroke?: boolean
+  /** 删除无用的 `fill-*` 属性，默认 `true` */
+  fill?: boolean
+  /** 删除 `stroke` 和 `fill` 都为 `none` 的图形，默认 `false` */
+  removeNone?: boolean
+}
+
+export interface RemoveXlinkConfig {
+  /** 同时改写 `<filter>`、`<tref>` 等不支持 SVG 2 `href` 的元素，默认 `false` */
+  includeLegacy?: boolean
+}
+
+export interface SortAttrsConfig {
+  /** 属性顺序，默认为 `id`、`width`、`height`、`x`、`x1` ... `d`、`points` */
+  order?: Array<string>
+  /** `front`（默认）把 `xmlns` 和 `xmlns:*` 排在最前，`alphabetical` 按字母顺序 */
+  xmlnsOrder?: string
+}
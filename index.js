@@ -393,4 +393,7 @@ if (!nativeBinding) {
 }
 
 module.exports = nativeBinding
+module.exports.XastElement = nativeBinding.XastElement
+module.exports.XastRoot = nativeBinding.XastRoot
+module.exports.builtinPlugins = nativeBinding.builtinPlugins
 module.exports.optimize = nativeBinding.optimize
@@ -0,0 +1,585 @@
+//! Node.js 绑定，`optimize` 的 JS 配置在这里转换为插件列表
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use bumpalo::Bump;
+use napi::bindgen_prelude::{Either, Either3, Function};
+use napi_derive::napi;
+use serde_json::{json, Value};
+
+use crate::js_plugin::{JsPlugin, JsPluginCallbacks};
+pub use crate::js_plugin::{XastElement, XastRoot};
+use crate::optimizer::{NamedPlugin, TraversalMode};
+use crate::parser::parse_svg;
+use crate::plugins::prefix_ids::{PrefixIdsPlugin, PrefixIdsPluginConfig, PrefixIdsPrefix};
+use crate::plugins::registry::{
+  create_plugins, default_plugins, skipped_plugins, PluginConfig, PluginContext, BUILTIN_PLUGINS,
+  PRESET_DEFAULT_PLUGINS,
+};
+use crate::DataUri;
+
+#[napi(object, object_to_js = false)]
+#[derive(Default)]
+pub struct OptimizeConfig<'a> {
+  /// 给根 `<svg>` 添加属性，已存在的属性不会被覆盖
+  #[napi(js_name = "addAttributesToSVGElement")]
+  pub add_attributes_to_svg_element: Option<AddAttributesToSVGElementConfig>,
+  /// 给根 `<svg>` 添加 class，与已有的 class 合并
+  #[napi(js_name = "addClassesToSVGElement")]
+  pub add_classes_to_svg_element: Option<AddClassesToSVGElementConfig>,
+  /// 清理属性值中的换行和多余空白，默认关闭
+  pub cleanup_attrs: Option<Either<bool, CleanupAttrsConfig>>,
+  /// 删除用不到的 `enable-background`，默认关闭
+  pub cleanup_enable_background: Option<bool>,
+  /// 把只有一个 `<stop>` 的渐变替换为纯色，默认关闭
+  pub convert_one_stop_gradients: Option<bool>,
+  /// 优化路径数据，目前会把 `<path>` 的 `transform` 应用到坐标上，默认关闭
+  pub convert_path_data: Option<Either<bool, ConvertPathDataConfig>>,
+  /// 合并属性和 `<stop>` 完全相同的渐变，默认关闭
+  pub merge_gradients: Option<bool>,
+  /// 把 `<g>` 的 `transform` 移到子元素上，默认关闭
+  pub move_group_attrs_to_elems: Option<bool>,
+  /// 被优化文件的路径，`prefixIds` 用它生成默认前缀
+  pub path: Option<String>,
+  /// svgo 格式的插件列表，可以使用 `preset-default`，设置后忽略其他插件开关
+  pub plugins: Option<Vec<Either<String, PluginConfigObject<'a>>>>,
+  /// 给 ID 和 class 加前缀，默认关闭
+  pub prefix_ids: Option<Either<bool, PrefixIdsConfig<'a>>>,
+  /// 按 `element:attribute:value` 规则删除属性
+  pub remove_attrs: Option<RemoveAttrsConfig>,
+  /// 删除指定 `id` 或 `class` 的元素
+  pub remove_elements_by_attr: Option<RemoveElementsByAttrConfig>,
+  /// 删除值为空的属性，默认关闭
+  pub remove_empty_attrs: Option<bool>,
+  /// 删除空的 `<text>`、`<tspan>` 和 `<tref>`，默认关闭
+  pub remove_empty_text: Option<Either<bool, RemoveEmptyTextConfig>>,
+  /// 删除 `<g>` 上不会被继承、也不作用于分组本身的表现属性，默认关闭
+  pub remove_non_inheritable_group_attrs: Option<bool>,
+  /// 删除完全位于 `viewBox` 之外的 `<path>`，默认关闭
+  pub remove_off_canvas_paths: Option<bool>,
+  /// 删除 JPEG、PNG、GIF 位图（包括 data URI），默认关闭
+  pub remove_raster_images: Option<bool>,
+  /// 删除 `<script>`、`on*` 事件属性和 `javascript:` 链接，默认关闭
+  pub remove_scripts: Option<bool>,
+  /// 删除 `<style>` 元素，默认关闭
+  pub remove_style_element: Option<bool>,
+  /// 删除不可见的 `stroke`/`fill` 相关属性，默认关闭
+  pub remove_useless_stroke_and_fill: Option<Either<bool, RemoveUselessStrokeAndFillConfig>>,
+  /// 移除根 `<svg>` 上没有被使用的 `xmlns:*` 声明，默认关闭
+  #[napi(js_name = "removeUnusedNS")]
+  pub remove_unused_ns: Option<bool>,
+  /// 移除 `<svg>` 的 `xmlns` 属性，用于内联到 HTML 中，默认关闭
+  #[napi(js_name = "removeXMLNS")]
+  pub remove_xmlns: Option<bool>,
+  /// 移除根 `<svg>` 的 `width`/`height`，缺少 `viewBox` 时根据尺寸补上
+  pub remove_dimensions: Option<bool>,
+  /// `viewBox` 与 `width`/`height` 一致时移除 `viewBox`，与 `removeDimensions` 同时开启时不生效
+  pub remove_view_box: Option<bool>,
+  /// 把 `xlink:href` 等属性改写为 SVG 2 的等价形式并移除 xlink 命名空间，默认关闭
+  pub remove_xlink: Option<Either<bool, RemoveXlinkConfig>>,
+  /// 把重复的 `<path>` 移到 `<defs>` 中并用 `<use>` 引用，默认关闭
+  pub reuse_paths: Option<bool>,
+  /// 按固定顺序排列属性以提高 gzip 压缩率，默认关闭
+  pub sort_attrs: Option<Either<bool, SortAttrsConfig>>,
+  /// 按出现频率排列 `<defs>` 的子元素，默认关闭
+  pub sort_defs_children: Option<bool>,
+  /// 把结果输出为 data URI：`base64`、`enc`（只做必要的转义）或 `unenc`
+  pub datauri: Option<String>,
+  /// 在结果中返回每个插件删除、修改的节点数和耗时，会让优化变慢，默认关闭
+  pub stats: Option<bool>,
+  /// `interleaved`（默认）所有插件共用一次遍历；`sequential` 与 svgo 相同，
+  /// 每个插件在前一个插件处理完整棵树后再遍历，只读写当前节点的相邻插件会合并遍历
+  pub traversal: Option<String>,
+}
+
+#[napi(object)]
+pub struct OptimizeOutput {
+  /// 优化后的 SVG，设置了 `datauri` 时是编码后的 data URI
+  pub data: String,
+  /// 每个插件的统计信息，按插件执行顺序排列，只有开启 `stats` 时才有
+  pub stats: Option<Vec<OptimizePluginStats>>,
+  /// 输入的字节数
+  pub original_size: u32,
+  /// 输出的字节数
+  pub optimized_size: u32,
+  /// 配置中请求了、但尚未实现而被跳过的 svgo 插件
+  pub skipped_plugins: Vec<String>,
+}
+
+#[napi(object)]
+pub struct OptimizePluginStats {
+  pub name: String,
+  /// 被删除的节点数，包括被删除节点的所有子孙节点
+  pub removed_nodes: u32,
+  /// 名字、属性、内容或子节点数量发生变化的节点数
+  pub modified_nodes: u32,
+  /// 插件所有钩子的耗时之和，单位微秒
+  pub elapsed_micros: f64,
+}
+
+/// 插件列表中的对象形式，与 svgo 的 `{ name, params }` 相同。
+/// 带有 `element` 或 `root` 回调时是自定义插件，此时忽略 `params`
+#[napi(object, object_to_js = false)]
+pub struct PluginConfigObject<'a> {
+  pub name: String,
+  pub params: Option<Value>,
+  /// 进入、离开每个元素时调用，在 `enter` 中调用 `remove()` 可以删除当前元素
+  pub element: Option<CustomElementVisitor<'a>>,
+  /// 遍历开始前、结束后各调用一次
+  pub root: Option<CustomRootVisitor<'a>>,
+}
+
+#[napi(object, object_to_js = false)]
+#[derive(Default)]
+pub struct CustomElementVisitor<'a> {
+  pub enter: Option<Function<'a, XastElement, ()>>,
+  pub exit: Option<Function<'a, XastElement, ()>>,
+}
+
+#[napi(object, object_to_js = false)]
+#[derive(Default)]
+pub struct CustomRootVisitor<'a> {
+  pub enter: Option<Function<'a, XastRoot, ()>>,
+  pub exit: Option<Function<'a, XastRoot, ()>>,
+}
+
+#[napi(object)]
+pub struct AddAttributesToSVGElementConfig {
+  /// 单个属性：只有名字的字符串，或 `{ name: value }` 对象
+  pub attribute: Option<Either<String, BTreeMap<String, String>>>,
+  /// 多个属性，每一项的格式与 `attribute` 相同
+  pub attributes: Option<Vec<Either<String, BTreeMap<String, String>>>>,
+}
+
+#[napi(object)]
+pub struct AddClassesToSVGElementConfig {
+  pub class_name: Option<String>,
+  pub class_names: Option<Vec<String>>,
+}
+
+#[napi(object)]
+pub struct CleanupAttrsConfig {
+  /// 删除换行，两侧都不是空白时替换为空格，默认 `true`
+  pub newlines: Option<bool>,
+  /// 去掉首尾空白，默认 `true`
+  pub trim: Option<bool>,
+  /// 把连续的空白合并为一个空格，默认 `true`
+  pub spaces: Option<bool>,
+}
+
+#[napi(object)]
+pub struct ConvertPathDataConfig {
+  /// 把 `transform` 应用到路径坐标上，默认 `true`
+  pub apply_transforms: Option<bool>,
+  /// 有描边的路径也应用 `transform`，默认 `true`
+  pub apply_transforms_stroked: Option<bool>,
+  /// 输出坐标的小数位数，默认 `3`
+  pub float_precision: Option<u32>,
+}
+
+#[napi(object)]
+pub struct RemoveAttrsConfig {
+  /// 一个或多个 `element:attribute:value` 规则，每段都是正则表达式
+  pub attrs: Either<String, Vec<String>>,
+  /// 规则中的分隔符，默认 `:`
+  pub elem_separator: Option<String>,
+  /// 保留值为 `currentColor` 的 `fill` 和 `stroke`，默认 `false`
+  pub preserve_current_color: Option<bool>,
+}
+
+#[napi(object)]
+pub struct RemoveElementsByAttrConfig {
+  pub id: Option<Either<String, Vec<String>>>,
+  pub class: Option<Either<String, Vec<String>>>,
+}
+
+#[napi(object)]
+pub struct RemoveEmptyTextConfig {
+  /// 删除没有子节点的 `<text>`，默认 `true`
+  pub text: Option<bool>,
+  /// 删除没有子节点的 `<tspan>`，默认 `true`
+  pub tspan: Option<bool>,
+  /// 删除没有 `xlink:href` 的 `<tref>`，默认 `true`
+  pub tref: Option<bool>,
+}
+
+#[napi(object)]
+pub struct RemoveUselessStrokeAndFillConfig {
+  /// 删除无用的 `stroke*` 属性，默认 `true`
+  pub stroke: Option<bool>,
+  /// 删除无用的 `fill-*` 属性，默认 `true`
+  pub fill: Option<bool>,
+  /// 删除 `stroke` 和 `fill` 都为 `none` 的图形，默认 `false`
+  pub remove_none: Option<bool>,
+}
+
+#[napi(object)]
+pub struct RemoveXlinkConfig {
+  /// 同时改写 `<filter>`、`<tref>` 等不支持 SVG 2 `href` 的元素，默认 `false`
+  pub include_legacy: Option<bool>,
+}
+
+#[napi(object)]
+pub struct SortAttrsConfig {
+  /// 属性顺序，默认为 `id`、`width`、`height`、`x`、`x1` ... `d`、`points`
+  pub order: Option<Vec<String>>,
+  /// `front`（默认）把 `xmlns` 和 `xmlns:*` 排在最前，`alphabetical` 按字母顺序
+  pub xmlns_order: Option<String>,
+}
+
+#[napi(object, object_to_js = false)]
+pub struct PrefixIdsConfig<'a> {
+  /// 前缀与原名之间的分隔符，默认 `__`
+  pub delim: Option<String>,
+  /// `false` 表示不加前缀；函数形式会对每个元素调用一次
+  pub prefix: Option<Either3<bool, String, Function<'a, PrefixIdsInfo, String>>>,
+  /// 是否给 `id` 及其引用加前缀，默认 `true`
+  pub prefix_ids: Option<bool>,
+  /// 是否给 `class` 加前缀，默认 `true`
+  pub prefix_class_names: Option<bool>,
+}
+
+/// 传给 `prefixIds.prefix` 回调的参数
+#[napi(object)]
+pub struct PrefixIdsInfo {
+  /// 当前元素的标签名
+  pub name: String,
+  pub path: Option<String>,
+}
+
+/// 内置插件的信息
+#[napi(object)]
+pub struct BuiltinPlugin {
+  pub name: String,
+  pub description: String,
+  /// 是否包含在 `preset-default` 中
+  pub preset_default: bool,
+}
+
+/// 列出所有可以在 `plugins` 中使用的内置插件。
+/// svgo 中尚未实现的插件不在列表中，它们出现在 `plugins` 中时会被直接跳过
+#[napi]
+pub fn builtin_plugins() -> Vec<BuiltinPlugin> {
+  BUILTIN_PLUGINS
+    .iter()
+    .map(|plugin| BuiltinPlugin {
+      name: plugin.name.to_string(),
+      description: plugin.description.to_string(),
+      preset_default: PRESET_DEFAULT_PLUGINS.contains(&plugin.name),
+    })
+    .collect()
+}
+
+#[napi]
+pub fn optimize(
+  input_xml: String,
+  config: Option<OptimizeConfig<'_>>,
+) -> napi::Result<OptimizeOutput> {
+  // 只有在 debug build 时才初始化 env_logger
+  if cfg!(debug_assertions) {
+    let _ = env_logger::try_init();
+  }
+  let mut config = config.unwrap_or_default();
+  let arena = Bump::new();
+  let root = parse_svg(&input_xml, &arena).map_err(|e| napi::Error::from_reason(e.to_string()))?;
+  // JS 回调抛出的异常，遍历结束后再返回给调用方
+  let callback_error: Rc<RefCell<Option<napi::Error>>> = Rc::new(RefCell::new(None));
+  let stats = config.stats.unwrap_or(false);
+  let mode = match config.traversal.as_deref() {
+    None | Some("interleaved") => TraversalMode::Interleaved,
+    Some("sequential") => TraversalMode::Sequential,
+    Some(other) => {
+      return Err(napi::Error::from_reason(format!(
+        "unknown traversal \"{}\", expected \"interleaved\" or \"sequential\"",
+        other
+      )))
+    }
+  };
+  let datauri = config
+    .datauri
+    .as_deref()
+    .map(str::parse::<DataUri>)
+    .transpose()
+    .map_err(napi::Error::from_reason)?;
+  let context = PluginContext {
+    arena: &arena,
+    path: config.path.clone(),
+    float_precision: None,
+  };
+  let mut plugins = Vec::new();
+  // 按名字创建的插件，用于报告被跳过的插件
+  let mut requested = Vec::new();
+  let mut prefix_ids_callback = None;
+  match config.plugins.take() {
+    Some(entries) => {
+      for entry in entries {
+        let plugin_config = match entry {
+          Either::A(name) => PluginConfig::new(&name, None),
+          Either::B(plugin) if plugin.element.is_some() || plugin.root.is_some() => {
+            let element = plugin.element.unwrap_or_default();
+            let root = plugin.root.unwrap_or_default();
+            let callbacks = JsPluginCallbacks {
+              element_enter: element.enter,
+              element_exit: element.exit,
+              root_enter: root.enter,
+              root_exit: root.exit,
+            };
+            let js_plugin = JsPlugin::new(&plugin.name, callbacks, callback_error.clone(), &arena);
+            plugins.push(NamedPlugin::new(&plugin.name, Box::new(js_plugin)));
+            continue;
+          }
+          Either::B(plugin) => PluginConfig::new(&plugin.name, plugin.params),
+        };
+        plugins.extend(
+          create_plugins(std::slice::from_ref(&plugin_config), &context)
+            .map_err(napi::Error::from_reason)?,
+        );
+        requested.push(plugin_config);
+      }
+    }
+    None => {
+      let (plugin_configs, callback) = legacy_plugin_configs(config)?;
+      plugins = create_plugins(&plugin_configs, &context).map_err(napi::Error::from_reason)?;
+      requested = plugin_configs;
+      prefix_ids_callback = callback;
+    }
+  }
+  // 函数形式的 prefix 无法用 JSON 表达，单独创建，它总是最后一个插件
+  if let Some(prefix_ids) = prefix_ids_callback {
+    let Some(Either3::C(callback)) = prefix_ids.prefix else {
+      unreachable!("only function prefixes are created here");
+    };
+    let callback_error = callback_error.clone();
+    let prefix = PrefixIdsPrefix::Dynamic(Box::new(move |el, path| {
+      let info = PrefixIdsInfo {
+        name: el.name.to_string(),
+        path: path.map(str::to_string),
+      };
+      callback.call(info).unwrap_or_else(|e| {
+        callback_error.borrow_mut().get_or_insert(e);
+        String::new()
+      })
+    }));
+    plugins.push(NamedPlugin::new(
+      "prefixIds",
+      Box::new(PrefixIdsPlugin::new(
+        PrefixIdsPluginConfig {
+          delim: prefix_ids.delim,
+          prefix,
+          prefix_ids: prefix_ids.prefix_ids,
+          prefix_class_names: prefix_ids.prefix_class_names,
+          path: context.path.clone(),
+        },
+        &arena,
+      )),
+    ));
+  }
+  let run_config = crate::Config {
+    stats,
+    traversal: mode,
+    ..Default::default()
+  };
+  let mut output = crate::run(&input_xml, root, plugins, &run_config);
+  if let Some(datauri) = datauri {
+    output.data = datauri.encode(&output.data);
+  }
+  if let Some(e) = callback_error.take() {
+    return Err(e);
+  }
+  let stats = output.stats.map(|stats| {
+    stats
+      .iter()
+      .map(|stats| OptimizePluginStats {
+        name: stats.name.clone(),
+        removed_nodes: stats.removed_nodes as u32,
+        modified_nodes: stats.modified_nodes as u32,
+        elapsed_micros: stats.elapsed.as_secs_f64() * 1e6,
+      })
+      .collect()
+  });
+  Ok(OptimizeOutput {
+    data: output.data,
+    stats,
+    original_size: output.original_size as u32,
+    optimized_size: output.optimized_size as u32,
+    skipped_plugins: skipped_plugins(&requested),
+  })
+}
+
+/// 把旧的插件开关转换为插件列表，顺序与之前手动创建插件时一致。
+/// `prefixIds.prefix` 为函数时无法转换，原样返回给调用方
+fn legacy_plugin_configs(
+  config: OptimizeConfig<'_>,
+) -> napi::Result<(Vec<PluginConfig>, Option<PrefixIdsConfig<'_>>)> {
+  let mut configs = default_plugins();
+  let mut enable = |name: &str, params: Option<Option<Value>>| {
+    if let Some(params) = params {
+      configs.push(PluginConfig::new(name, params));
+    }
+  };
+  enable(
+    "cleanupAttrs",
+    toggle(
+      config.cleanup_attrs,
+      |c| json!({ "newlines": c.newlines, "trim": c.trim, "spaces": c.spaces }),
+    ),
+  );
+  enable(
+    "cleanupEnableBackground",
+    flag(config.cleanup_enable_background),
+  );
+  enable("removeUnusedNS", flag(config.remove_unused_ns));
+  enable("removeEmptyAttrs", flag(config.remove_empty_attrs));
+  enable(
+    "removeEmptyText",
+    toggle(
+      config.remove_empty_text,
+      |c| json!({ "text": c.text, "tspan": c.tspan, "tref": c.tref }),
+    ),
+  );
+  enable(
+    "removeUselessStrokeAndFill",
+    toggle(
+      config.remove_useless_stroke_and_fill,
+      |c| json!({ "stroke": c.stroke, "fill": c.fill, "removeNone": c.remove_none }),
+    ),
+  );
+  enable(
+    "removeNonInheritableGroupAttrs",
+    flag(config.remove_non_inheritable_group_attrs),
+  );
+  enable(
+    "moveGroupAttrsToElems",
+    flag(config.move_group_attrs_to_elems),
+  );
+  enable(
+    "convertPathData",
+    toggle(config.convert_path_data, |c| {
+      json!({
+        "applyTransforms": c.apply_transforms,
+        "applyTransformsStroked": c.apply_transforms_stroked,
+        "floatPrecision": c.float_precision,
+      })
+    }),
+  );
+  enable(
+    "convertOneStopGradients",
+    flag(config.convert_one_stop_gradients),
+  );
+  enable("mergeGradients", flag(config.merge_gradients));
+  enable("removeRasterImages", flag(config.remove_raster_images));
+  enable("removeScripts", flag(config.remove_scripts));
+  enable("removeStyleElement", flag(config.remove_style_element));
+  enable("removeOffCanvasPaths", flag(config.remove_off_canvas_paths));
+  if config.remove_dimensions.unwrap_or(false) {
+    enable("removeDimensions", Some(None));
+  } else {
+    enable("removeViewBox", flag(config.remove_view_box));
+  }
+  enable(
+    "addAttributesToSVGElement",
+    config.add_attributes_to_svg_element.map(|c| {
+      let attribute = |attribute| match attribute {
+        Either::A(name) => Value::String(name),
+        Either::B(map) => json!(map),
+      };
+      Some(json!({
+        "attribute": c.attribute.map(attribute),
+        "attributes": c.attributes.map(|items| items.into_iter().map(attribute).collect::<Vec<_>>()),
+      }))
+    }),
+  );
+  enable(
+    "addClassesToSVGElement",
+    config
+      .add_classes_to_svg_element
+      .map(|c| Some(json!({ "className": c.class_name, "classNames": c.class_names }))),
+  );
+  enable(
+    "removeAttrs",
+    config.remove_attrs.map(|c| {
+      Some(json!({
+        "attrs": one_or_many(c.attrs),
+        "elemSeparator": c.elem_separator,
+        "preserveCurrentColor": c.preserve_current_color,
+      }))
+    }),
+  );
+  enable(
+    "removeElementsByAttr",
+    config.remove_elements_by_attr.map(|c| {
+      Some(json!({
+        "id": c.id.map(one_or_many),
+        "class": c.class.map(one_or_many),
+      }))
+    }),
+  );
+  enable(
+    "removeXlink",
+    toggle(
+      config.remove_xlink,
+      |c| json!({ "includeLegacy": c.include_legacy }),
+    ),
+  );
+  enable("removeXMLNS", flag(config.remove_xmlns));
+  enable("reusePaths", flag(config.reuse_paths));
+  enable(
+    "sortAttrs",
+    toggle(
+      config.sort_attrs,
+      |c| json!({ "order": c.order, "xmlnsOrder": c.xmlns_order }),
+    ),
+  );
+  enable("sortDefsChildren", flag(config.sort_defs_children));
+  let mut prefix_ids_callback = None;
+  match config.prefix_ids {
+    Some(Either::A(true)) => enable("prefixIds", Some(None)),
+    Some(Either::B(prefix_ids)) if matches!(prefix_ids.prefix, Some(Either3::C(_))) => {
+      prefix_ids_callback = Some(prefix_ids);
+    }
+    Some(Either::B(prefix_ids)) => {
+      let prefix = match prefix_ids.prefix {
+        Some(Either3::A(prefix)) => json!(prefix),
+        Some(Either3::B(prefix)) => json!(prefix),
+        Some(Either3::C(_)) | None => Value::Null,
+      };
+      enable(
+        "prefixIds",
+        Some(Some(json!({
+          "delim": prefix_ids.delim,
+          "prefix": prefix,
+          "prefixIds": prefix_ids.prefix_ids,
+          "prefixClassNames": prefix_ids.prefix_class_names,
+        }))),
+      );
+    }
+    Some(Either::A(false)) | None => {}
+  }
+  Ok((configs, prefix_ids_callback))
+}
+
+/// 只有开关的插件：开启时使用默认参数
+fn flag(enabled: Option<bool>) -> Option<Option<Value>> {
+  enabled.unwrap_or(false).then_some(None)
+}
+
+/// `true` 使用默认参数，对象形式转换为插件参数
+fn toggle<T>(
+  value: Option<Either<bool, T>>,
+  params: impl FnOnce(T) -> Value,
+) -> Option<Option<Value>> {
+  match value {
+    Some(Either::A(true)) => Some(None),
+    Some(Either::B(config)) => Some(Some(params(config))),
+    Some(Either::A(false)) | None => None,
+  }
+}
+
+/// svgo 的参数常常既可以是单个字符串也可以是数组
+fn one_or_many(value: Either<String, Vec<String>>) -> Vec<String> {
+  match value {
+    Either::A(one) => vec![one],
+    Either::B(many) => many,
+  }
+}
@@ -0,0 +1,463 @@
+//! svgo 格式的配置文件
+//!
+//! 支持 `svgo.config.json` 和 `svgo-rs.toml` 两种格式，字段与 `svgo.config.js` 导出的对象相同。
+//! `extends` 指定的基础配置先被读取，当前文件中的字段覆盖基础配置，
+//! `plugins` 中同名的插件替换基础配置中的那一项，其他插件追加到末尾。
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bumpalo::Bump;
+use serde_json::{Map, Value};
+
+use crate::plugins::registry::did_you_mean;
+use crate::{create_plugins, skipped_plugins, Config, Error, PluginConfig, PluginContext};
+
+/// 按优先级排列的配置文件名，同一目录下只使用第一个存在的文件
+pub const CONFIG_FILES: [&str; 2] = ["svgo.config.json", "svgo-rs.toml"];
+
+const OPTIONS: [&str; 6] = [
+  "datauri",
+  "floatPrecision",
+  "js2svg",
+  "multipass",
+  "path",
+  "plugins",
+];
+
+/// 从 `dir` 开始逐级向上查找配置文件
+pub fn find_config_file(dir: &Path) -> Option<PathBuf> {
+  dir
+    .ancestors()
+    .find_map(|dir| CONFIG_FILES.iter().map(|name| dir.join(name)).find(|path| path.is_file()))
+}
+
+impl Config {
+  /// 解析 `svgo.config.json`，格式与 `svgo.config.js` 导出的对象相同，`extends` 只能在 [`Config::load`] 中使用
+  pub fn from_json(json: &str) -> Result<Config, Error> {
+    let value = serde_json::from_str(json).map_err(|e| Error::Config(e.to_string()))?;
+    Config::from_value(value)
+  }
+
+  /// 解析 `svgo-rs.toml`，字段与 JSON 格式相同
+  pub fn from_toml(toml: &str) -> Result<Config, Error> {
+    let value = toml::from_str(toml).map_err(|e| Error::Config(e.to_string()))?;
+    Config::from_value(value)
+  }
+
+  /// 读取配置文件并合并 `extends` 指定的基础配置，按扩展名区分 JSON 和 TOML。
+  /// 会检查插件名和插件参数，错误信息中带有出错的文件
+  pub fn load(path: &Path) -> Result<Config, Error> {
+    let value = load_value(path, &mut Vec::new())?;
+    let config = Config::from_value(Value::Object(value))
+      .map_err(|e| Error::Config(format!("{}: {}", path.display(), e)))?;
+    config
+      .validate()
+      .map_err(|e| Error::Config(format!("{}: {}", path.display(), e)))?;
+    Ok(config)
+  }
+
+  /// 检查插件名和参数是否正确，与优化时创建插件的检查相同，返回会被跳过的插件
+  pub fn validate(&self) -> Result<Vec<String>, Error> {
+    let Some(plugins) = &self.plugins else {
+      return Ok(Vec::new());
+    };
+    let context = PluginContext {
+      arena: &Bump::new(),
+      path: self.path.clone(),
+      float_precision: self.float_precision,
+    };
+    create_plugins(plugins, &context).map_err(Error::Config)?;
+    Ok(skipped_plugins(plugins))
+  }
+
+  fn from_value(value: Value) -> Result<Config, Error> {
+    let Value::Object(object) = value else {
+      return Err(Error::Config("config must be an object".to_string()));
+    };
+    if object.contains_key("extends") {
+      return Err(Error::Config(
+        "\"extends\" is only supported when loading a config file".to_string(),
+      ));
+    }
+    if let Some(key) = object.keys().find(|key| !OPTIONS.contains(&key.as_str())) {
+      return Err(Error::Config(format!(
+        "unknown option \"{}\"{}",
+        key,
+        did_you_mean(key, OPTIONS.iter().copied())
+      )));
+    }
+    let mut config = Config::default();
+    if let Some(plugins) = object.get("plugins") {
+      config.plugins = Some(parse_plugins(plugins)?);
+    }
+    config.path = string(&object, "path")?;
+    config.multipass = boolean(&object, "multipass")?.unwrap_or(false);
+    config.float_precision = integer(&object, "floatPrecision")?.map(|n| n as u32);
+    config.datauri = string(&object, "datauri")?
+      .map(|datauri| datauri.parse())
+      .transpose()
+      .map_err(Error::Config)?;
+    match object.get("js2svg") {
+      Some(Value::Object(js2svg)) => {
+        config.pretty = boolean(js2svg, "pretty")?.unwrap_or(false);
+        config.indent = integer(js2svg, "indent")?.map(|n| n as usize);
+      }
+      Some(_) => return Err(Error::Config("\"js2svg\" must be an object".to_string())),
+      None => {}
+    }
+    Ok(config)
+  }
+}
+
+/// 读取配置文件并展开 `extends`，`chain` 记录正在读取的文件，用于发现循环引用
+fn load_value(path: &Path, chain: &mut Vec<PathBuf>) -> Result<Map<String, Value>, Error> {
+  let error = |message: String| Error::Config(format!("{}: {}", path.display(), message));
+  let canonical = path.canonicalize().map_err(|e| error(e.to_string()))?;
+  if chain.contains(&canonical) {
+    let cycle: Vec<String> = chain
+      .iter()
+      .chain([&canonical])
+      .map(|path| path.display().to_string())
+      .collect();
+    return Err(Error::Config(format!(
+      "circular extends: {}",
+      cycle.join(" -> ")
+    )));
+  }
+  let text = fs::read_to_string(path).map_err(|e| error(e.to_string()))?;
+  let value: Value = if path.extension().is_some_and(|ext| ext == "toml") {
+    toml::from_str(&text).map_err(|e| error(e.to_string()))?
+  } else {
+    serde_json::from_str(&text).map_err(|e| error(e.to_string()))?
+  };
+  let Value::Object(mut object) = value else {
+    return Err(error("config must be an object".to_string()));
+  };
+
+  let bases = match object.remove("extends") {
+    None => Vec::new(),
+    Some(Value::String(base)) => vec![base],
+    Some(Value::Array(bases)) => bases
+      .into_iter()
+      .map(|base| match base {
+        Value::String(base) => Ok(base),
+        _ => Err(error(
+          "\"extends\" must be a path or a list of paths".to_string(),
+        )),
+      })
+      .collect::<Result<_, _>>()?,
+    Some(_) => {
+      return Err(error(
+        "\"extends\" must be a path or a list of paths".to_string(),
+      ))
+    }
+  };
+  // 基础配置的路径相对于当前文件所在的目录
+  let dir = path.parent().unwrap_or(Path::new(""));
+  chain.push(canonical);
+  let mut merged = Map::new();
+  for base in bases {
+    merge(&mut merged, load_value(&dir.join(base), chain)?);
+  }
+  chain.pop();
+  merge(&mut merged, object);
+  Ok(merged)
+}
+
+fn merge(base: &mut Map<String, Value>, config: Map<String, Value>) {
+  for (key, value) in config {
+    match (base.get_mut(&key), value) {
+      (Some(Value::Array(base_plugins)), Value::Array(plugins)) if key == "plugins" => {
+        for plugin in plugins {
+          let name = plugin_name(&plugin);
+          match base_plugins.iter_mut().find(|base| name.is_some() && plugin_name(base) == name) {
+            Some(base) => *base = plugin,
+            None => base_plugins.push(plugin),
+          }
+        }
+      }
+      (Some(Value::Object(base)), Value::Object(value)) => merge(base, value),
+      (_, value) => {
+        base.insert(key, value);
+      }
+    }
+  }
+}
+
+fn plugin_name(plugin: &Value) -> Option<String> {
+  match plugin {
+    Value::String(name) => Some(name.clone()),
+    Value::Object(object) => object.get("name").and_then(Value::as_str).map(str::to_string),
+    _ => None,
+  }
+}
+
+/// `plugins` 中的每一项可以是插件名或 `{ name, params }`
+fn parse_plugins(value: &Value) -> Result<Vec<PluginConfig>, Error> {
+  let Value::Array(items) = value else {
+    return Err(Error::Config("\"plugins\" must be an array".to_string()));
+  };
+  items
+    .iter()
+    .map(|item| match item {
+      Value::String(name) => Ok(PluginConfig::new(name, None)),
+      Value::Object(object) => {
+        let name = string(object, "name")?
+          .ok_or_else(|| Error::Config("plugin is missing \"name\"".to_string()))?;
+        Ok(PluginConfig::new(&name, object.get("params").cloned()))
+      }
+      _ => Err(Error::Config(
+        "plugin must be a name or an object with \"name\" and \"params\"".to_string(),
+      )),
+    })
+    .collect()
+}
+
+fn string(object: &Map<String, Value>, key: &str) -> Result<Option<String>, Error> {
+  match object.get(key) {
+    None | Some(Value::Null) => Ok(None),
+    Some(Value::String(value)) => Ok(Some(value.clone())),
+    Some(_) => Err(Error::Config(format!("\"{}\" must be a string", key))),
+  }
+}
+
+fn boolean(object: &Map<String, Value>, key: &str) -> Result<Option<bool>, Error> {
+  match object.get(key) {
+    None | Some(Value::Null) => Ok(None),
+    Some(Value::Bool(value)) => Ok(Some(*value)),
+    Some(_) => Err(Error::Config(format!("\"{}\" must be a boolean", key))),
+  }
+}
+
+fn integer(object: &Map<String, Value>, key: &str) -> Result<Option<u64>, Error> {
+  match object.get(key) {
+    None | Some(Value::Null) => Ok(None),
+    Some(value) => value
+      .as_u64()
+      .map(Some)
+      .ok_or_else(|| Error::Config(format!("\"{}\" must be a non-negative integer", key))),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::{Path, PathBuf};
+  use std::{fs, process};
+
+  use super::*;
+  use crate::DataUri;
+
+  /// 测试结束时删除的临时目录
+  struct TempDir(PathBuf);
+
+  impl TempDir {
+    fn new(name: &str) -> Self {
+      let dir = std::env::temp_dir().join(format!("svgo-rs-{}-{}", name, process::id()));
+      let _ = fs::remove_dir_all(&dir);
+      fs::create_dir_all(&dir).unwrap();
+      TempDir(dir)
+    }
+
+    fn write(&self, path: &str, content: &str) -> PathBuf {
+      let path = self.0.join(path);
+      fs::create_dir_all(path.parent().unwrap()).unwrap();
+      fs::write(&path, content).unwrap();
+      path
+    }
+  }
+
+  impl Drop for TempDir {
+    fn drop(&mut self) {
+      let _ = fs::remove_dir_all(&self.0);
+    }
+  }
+
+  fn plugin_names(config: &Config) -> Vec<&str> {
+    config.plugins.iter().flatten().map(|plugin| plugin.name.as_str()).collect()
+  }
+
+  fn load_error(path: &Path) -> String {
+    Config::load(path).unwrap_err().to_string()
+  }
+
+  #[test]
+  fn find_config_file_walks_up_and_prefers_json() {
+    let dir = TempDir::new("find");
+    let json = dir.write("svgo.config.json", "{}");
+    let toml = dir.write("pkg/svgo-rs.toml", "");
+    fs::create_dir_all(dir.0.join("pkg/icons/nested")).unwrap();
+    fs::create_dir_all(dir.0.join("other")).unwrap();
+
+    assert_eq!(
+      find_config_file(&dir.0.join("pkg/icons/nested")),
+      Some(toml)
+    );
+    assert_eq!(find_config_file(&dir.0.join("other")), Some(json.clone()));
+
+    dir.write("pkg/svgo.config.json", "{}");
+    assert_eq!(
+      find_config_file(&dir.0.join("pkg/icons")),
+      Some(dir.0.join("pkg/svgo.config.json"))
+    );
+    fs::remove_file(&json).unwrap();
+    assert_eq!(find_config_file(&dir.0.join("other")), None);
+  }
+
+  #[test]
+  fn load_parses_json_and_toml() {
+    let dir = TempDir::new("formats");
+    let json = dir.write(
+      "svgo.config.json",
+      r#"{ "multipass": true, "floatPrecision": 2, "datauri": "enc", "js2svg": { "pretty": true, "indent": 2 }, "plugins": ["removeTitle", { "name": "removeDesc", "params": { "removeAny": true } }] }"#,
+    );
+    let config = Config::load(&json).unwrap();
+    assert!(config.multipass && config.pretty);
+    assert_eq!(config.float_precision, Some(2));
+    assert_eq!(config.indent, Some(2));
+    assert_eq!(config.datauri, Some(DataUri::Enc));
+    assert_eq!(plugin_names(&config), ["removeTitle", "removeDesc"]);
+
+    let toml = dir.write(
+      "svgo-rs.toml",
+      r#"
+multipass = true
+plugins = ["removeTitle", { name = "removeDesc", params = { removeAny = true } }]
+
+[js2svg]
+indent = 1
+"#,
+    );
+    let config = Config::load(&toml).unwrap();
+    assert!(config.multipass && !config.pretty);
+    assert_eq!(config.indent, Some(1));
+    assert_eq!(plugin_names(&config), ["removeTitle", "removeDesc"]);
+    assert_eq!(
+      config.plugins.unwrap()[1].params,
+      Some(serde_json::json!({ "removeAny": true }))
+    );
+  }
+
+  #[test]
+  fn extends_merges_base_configs_relative_to_the_file() {
+    let dir = TempDir::new("extends");
+    dir.write(
+      "svgo.config.json",
+      r#"{ "multipass": true, "js2svg": { "pretty": true, "indent": 4 }, "plugins": ["preset-default", "removeTitle"] }"#,
+    );
+    dir.write("shared/xlink.json", r#"{ "plugins": ["removeXlink"] }"#);
+    let package = dir.write(
+      "packages/icons/svgo-rs.toml",
+      r#"
+extends = ["../../svgo.config.json", "../../shared/xlink.json"]
+plugins = [
+  { name = "preset-default", params = { overrides = { removeViewBox = false } } },
+  "removeDimensions",
+]
+
+[js2svg]
+indent = 2
+"#,
+    );
+    let config = Config::load(&package).unwrap();
+    assert!(config.multipass && config.pretty);
+    assert_eq!(config.indent, Some(2));
+    // 同名插件替换原来的位置，新插件追加到末尾
+    assert_eq!(
+      plugin_names(&config),
+      [
+        "preset-default",
+        "removeTitle",
+        "removeXlink",
+        "removeDimensions"
+      ]
+    );
+    assert_eq!(
+      config.plugins.unwrap()[0].params,
+      Some(serde_json::json!({ "overrides": { "removeViewBox": false } }))
+    );
+
+    let single = dir.write(
+      "packages/single.json",
+      r#"{ "extends": "../shared/xlink.json" }"#,
+    );
+    assert_eq!(
+      plugin_names(&Config::load(&single).unwrap()),
+      ["removeXlink"]
+    );
+  }
+
+  #[test]
+  fn circular_extends_is_an_error() {
+    let dir = TempDir::new("circular");
+    let a = dir.write("a.json", r#"{ "extends": "nested/b.json" }"#);
+    dir.write("nested/b.json", r#"{ "extends": ["../a.json"] }"#);
+    let error = load_error(&a);
+    assert!(error.starts_with("circular extends: "), "{}", error);
+    assert!(error.contains("b.json -> "), "{}", error);
+    assert!(error.ends_with("a.json"), "{}", error);
+
+    let itself = dir.write("itself.json", r#"{ "extends": "./itself.json" }"#);
+    assert!(load_error(&itself).starts_with("circular extends: "));
+  }
+
+  #[test]
+  fn invalid_configs_report_the_file_and_a_hint() {
+    let dir = TempDir::new("invalid");
+    let cases = [
+      (
+        r#"{ "multipass": "yes" }"#,
+        r#""multipass" must be a boolean"#,
+      ),
+      (
+        r#"{ "plugin": [] }"#,
+        r#"unknown option "plugin", did you mean "plugins"?"#,
+      ),
+      (r#"{ "js2svg": true }"#, r#""js2svg" must be an object"#),
+      (r#"{ "datauri": "utf8" }"#, r#"unknown datauri "utf8""#),
+      (
+        r#"{ "plugins": [{ "params": {} }] }"#,
+        r#"plugin is missing "name""#,
+      ),
+      (
+        r#"{ "plugins": ["removeViewBo"] }"#,
+        r#"unknown plugin "removeViewBo", did you mean "removeViewBox"?"#,
+      ),
+      (
+        r#"{ "plugins": [{ "name": "removeDesc", "params": { "removeAny": "yes" } }] }"#,
+        "removeDesc: `removeAny` must be a boolean",
+      ),
+      (
+        r#"{ "extends": 1 }"#,
+        r#""extends" must be a path or a list of paths"#,
+      ),
+      ("[]", "config must be an object"),
+    ];
+    for (content, expected) in cases {
+      let path = dir.write("svgo.config.json", content);
+      let error = load_error(&path);
+      assert!(error.starts_with(&path.display().to_string()), "{}", error);
+      assert!(
+        error.contains(expected),
+        "{} does not contain {}",
+        error,
+        expected
+      );
+    }
+
+    // 找不到基础配置时报告基础配置的路径
+    let path = dir.write("svgo.config.json", r#"{ "extends": "missing.json" }"#);
+    assert!(load_error(&path).starts_with(&dir.0.join("missing.json").display().to_string()));
+
+    let toml = dir.write("svgo-rs.toml", "multipass = ");
+    assert!(load_error(&toml).starts_with(&toml.display().to_string()));
+  }
+
+  #[test]
+  fn extends_is_only_supported_in_files() {
+    let error = Config::from_json(r#"{ "extends": "base.json" }"#).unwrap_err();
+    assert_eq!(
+      error,
+      Error::Config("\"extends\" is only supported when loading a config file".to_string())
+    );
+  }
+}
@@ -0,0 +1,126 @@
+//! 把优化结果输出为 data URI，与 svgo 的 `datauri` 选项相同
+use std::str::FromStr;
+
+const BASE64_CHARS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// data URI 的编码方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataUri {
+  /// `data:image/svg+xml;base64,...`
+  Base64,
+  /// `data:image/svg+xml,...`，只转义 URI 中不允许出现的字符，空格和双引号保持原样，
+  /// 单引号会被转义，可以直接放进 CSS 的 `url('...')`
+  Enc,
+  /// `data:image/svg+xml,...`，不做任何转义
+  Unenc,
+}
+
+impl DataUri {
+  pub fn encode(self, svg: &str) -> String {
+    match self {
+      DataUri::Base64 => format!("data:image/svg+xml;base64,{}", base64(svg.as_bytes())),
+      DataUri::Enc => format!("data:image/svg+xml,{}", escape(svg)),
+      DataUri::Unenc => format!("data:image/svg+xml,{}", svg),
+    }
+  }
+}
+
+impl FromStr for DataUri {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "base64" => Ok(DataUri::Base64),
+      "enc" => Ok(DataUri::Enc),
+      "unenc" => Ok(DataUri::Unenc),
+      _ => Err(format!(
+        "unknown datauri \"{}\", expected \"base64\", \"enc\" or \"unenc\"",
+        s
+      )),
+    }
+  }
+}
+
+fn base64(bytes: &[u8]) -> String {
+  let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+  for chunk in bytes.chunks(3) {
+    let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - i * 8));
+    for i in 0..4 {
+      if i <= chunk.len() {
+        out.push(BASE64_CHARS[(n >> (18 - i * 6)) as usize & 63] as char);
+      } else {
+        out.push('=');
+      }
+    }
+  }
+  out
+}
+
+/// 转义控制字符、非 ASCII 字符和 URI 中有特殊含义或不安全的字符
+fn escape(svg: &str) -> String {
+  let mut out = String::with_capacity(svg.len());
+  for c in svg.chars() {
+    match c {
+      '%' | '#' | '<' | '>' | '\'' | '\\' | '^' | '`' | '{' | '|' | '}' | '[' | ']' => {
+        out.push_str(&format!("%{:02X}", c as u32));
+      }
+      ' ' | '"' => out.push(c),
+      c if c.is_ascii_graphic() => out.push(c),
+      c => {
+        let mut buf = [0; 4];
+        for b in c.encode_utf8(&mut buf).bytes() {
+          out.push_str(&format!("%{:02X}", b));
+        }
+      }
+    }
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn base64_pads_incomplete_chunks() {
+    assert_eq!(base64(b""), "");
+    assert_eq!(base64(b"a"), "YQ==");
+    assert_eq!(base64(b"ab"), "YWI=");
+    assert_eq!(base64(b"abc"), "YWJj");
+    assert_eq!(base64(b"abcd"), "YWJjZA==");
+    assert_eq!(base64(&[0xff, 0xfe, 0xfd]), "//79");
+  }
+
+  #[test]
+  fn escape_keeps_spaces_and_double_quotes() {
+    assert_eq!(escape("100% #a 'b' \"c\""), "100%25 %23a %27b%27 \"c\"");
+    assert_eq!(escape("<a\\b>{[|]}^`"), "%3Ca%5Cb%3E%7B%5B%7C%5D%7D%5E%60");
+    assert_eq!(escape("\t\n"), "%09%0A");
+  }
+
+  #[test]
+  fn escape_encodes_non_ascii_as_utf8_bytes() {
+    assert_eq!(escape("é"), "%C3%A9");
+    assert_eq!(escape("中"), "%E4%B8%AD");
+    assert_eq!(escape("😀"), "%F0%9F%98%80");
+  }
+
+  #[test]
+  fn encode_adds_the_svg_media_type() {
+    let svg = "<svg id='é'/>";
+    assert_eq!(
+      DataUri::Base64.encode("<svg/>"),
+      "data:image/svg+xml;base64,PHN2Zy8+"
+    );
+    assert_eq!(
+      DataUri::Enc.encode(svg),
+      "data:image/svg+xml,%3Csvg id=%27%C3%A9%27/%3E"
+    );
+    assert_eq!(
+      DataUri::Unenc.encode(svg),
+      "data:image/svg+xml,<svg id='é'/>"
+    );
+    assert_eq!("enc".parse(), Ok(DataUri::Enc));
+    assert!("utf8".parse::<DataUri>().is_err());
+  }
+}
@@ -0,0 +1,400 @@
+//! 把 JS 编写的自定义插件接入 Rust 的遍历
+//!
+//! 回调拿到的 `XastElement`/`XastRoot` 只记录从回调节点出发的子节点下标，每次访问都从
+//! `ViewScope` 保存的回调节点重新找到对应元素，不会同时持有多个可变引用。
+//! 回调期间不会改变任何 `children` 的结构，`remove()` 只做标记，回调返回后再统一删除，
+//! 所以下标在回调期间始终有效。
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+use napi::bindgen_prelude::{Function, JsValuesTupleIntoVec};
+use napi_derive::napi;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::{XMLAstChild, XMLAstElement, XMLAstRoot};
+
+/// 回调的参数对应的节点
+#[derive(Clone, Copy)]
+enum Base {
+  Root(*mut XMLAstRoot<'static>),
+  Element(*mut XMLAstElement<'static>),
+}
+
+/// 一次回调中创建的所有视图共享的状态
+struct ViewScope {
+  alive: Cell<bool>,
+  arena: *const Bump,
+  base: Base,
+  /// 回调中调用了 `remove()` 的元素的路径
+  removed: RefCell<Vec<Vec<usize>>>,
+}
+
+impl ViewScope {
+  fn new(arena: &Bump, base: Base) -> Rc<Self> {
+    Rc::new(ViewScope {
+      alive: Cell::new(true),
+      arena,
+      base,
+      removed: RefCell::new(Vec::new()),
+    })
+  }
+
+  fn check(&self) -> napi::Result<()> {
+    if self.alive.get() {
+      Ok(())
+    } else {
+      Err(napi::Error::from_reason(
+        "the node can only be accessed inside the plugin callback it was passed to",
+      ))
+    }
+  }
+
+  /// 按路径找到元素并调用 `f`，引用只在 `f` 执行期间存在
+  fn element<R>(
+    &self,
+    path: &[usize],
+    f: impl FnOnce(&mut XMLAstElement<'static>) -> R,
+  ) -> napi::Result<R> {
+    self.check()?;
+    // SAFETY: scope 存活说明回调还没有返回，调用方在此期间不会访问回调节点，
+    // 节点也没有被移动或释放；每次访问都从 `base` 重新借用，之前的引用已经结束
+    let (mut element, path) = match self.base {
+      Base::Element(element) => (unsafe { &mut *element }, path),
+      Base::Root(root) => {
+        let (first, rest) = path.split_first().expect("root views always have a path");
+        (
+          child_element(unsafe { &mut (*root).children }, *first),
+          rest,
+        )
+      }
+    };
+    for &index in path {
+      element = child_element(&mut element.children, index);
+    }
+    Ok(f(element))
+  }
+
+  /// `base` 为根节点时顶层元素的下标
+  fn root_children(&self) -> napi::Result<Vec<usize>> {
+    self.check()?;
+    let Base::Root(root) = self.base else {
+      unreachable!("root views are only created for root callbacks");
+    };
+    // SAFETY: 同 `ViewScope::element`
+    Ok(element_indices(unsafe { &(*root).children }))
+  }
+
+  /// 把字符串放进 arena，与 AST 中其他字符串的生命周期相同
+  fn alloc_str(&self, value: &str) -> &'static str {
+    // SAFETY: 只在回调期间调用，此时 arena 一定存活，而 AST 本身也只在 arena 存活期间使用
+    unsafe { &*((*self.arena).alloc_str(value) as *const str) }
+  }
+}
+
+fn child_element<'v>(
+  children: &'v mut BumpVec<'static, XMLAstChild<'static>>,
+  index: usize,
+) -> &'v mut XMLAstElement<'static> {
+  match &mut children[index] {
+    XMLAstChild::Element(el) => el,
+    _ => unreachable!("view paths only point at elements"),
+  }
+}
+
+fn element_indices(children: &[XMLAstChild]) -> Vec<usize> {
+  children
+    .iter()
+    .enumerate()
+    .filter(|(_, child)| matches!(child, XMLAstChild::Element(_)))
+    .map(|(index, _)| index)
+    .collect()
+}
+
+/// 传给 `element.enter`/`element.exit` 回调的元素
+#[napi]
+pub struct XastElement {
+  /// 从回调节点到该元素经过的 `children` 下标
+  path: Vec<usize>,
+  scope: Rc<ViewScope>,
+}
+
+impl XastElement {
+  fn element<R>(&self, f: impl FnOnce(&mut XMLAstElement<'static>) -> R) -> napi::Result<R> {
+    self.scope.element(&self.path, f)
+  }
+
+  fn child_views(&self, indices: Vec<usize>) -> Vec<XastElement> {
+    indices
+      .into_iter()
+      .map(|index| XastElement {
+        path: [self.path.as_slice(), &[index]].concat(),
+        scope: self.scope.clone(),
+      })
+      .collect()
+  }
+}
+
+#[napi]
+impl XastElement {
+  /// 标签名
+  #[napi(getter)]
+  pub fn name(&self) -> napi::Result<String> {
+    self.element(|el| el.name.to_string())
+  }
+
+  #[napi(setter, js_name = "name")]
+  pub fn set_name(&self, name: String) -> napi::Result<()> {
+    let name = self.scope.alloc_str(&name);
+    self.element(|el| el.name = name)
+  }
+
+  /// 所有属性的副本，修改它不会影响元素
+  #[napi(getter)]
+  pub fn attributes(&self) -> napi::Result<BTreeMap<String, String>> {
+    self.element(|el| {
+      el.attributes
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+    })
+  }
+
+  #[napi]
+  pub fn get_attribute(&self, name: String) -> napi::Result<Option<String>> {
+    self.element(|el| el.get_attribute(&name).map(str::to_string))
+  }
+
+  #[napi]
+  pub fn has_attribute(&self, name: String) -> napi::Result<bool> {
+    self.element(|el| el.has_attribute(&name))
+  }
+
+  /// 已存在时修改属性值，否则添加到末尾
+  #[napi]
+  pub fn set_attribute(&self, name: String, value: String) -> napi::Result<()> {
+    let value = self.scope.alloc_str(&value);
+    self.element(
+      |el| match el.attributes.iter_mut().find(|(n, _)| *n == name) {
+        Some((_, v)) => *v = value,
+        None => el.attributes.push((self.scope.alloc_str(&name), value)),
+      },
+    )
+  }
+
+  #[napi]
+  pub fn remove_attribute(&self, name: String) -> napi::Result<()> {
+    self.element(|el| el.remove_attribute(&name))
+  }
+
+  /// 子元素，不包括文本、注释等其他节点
+  #[napi(getter)]
+  pub fn children(&self) -> napi::Result<Vec<XastElement>> {
+    let indices = self.element(|el| element_indices(&el.children))?;
+    Ok(self.child_views(indices))
+  }
+
+  /// 所有子孙文本节点的内容
+  #[napi(getter)]
+  pub fn text_content(&self) -> napi::Result<String> {
+    self.element(|el| {
+      let mut text = String::new();
+      collect_text(&el.children, &mut text);
+      text
+    })
+  }
+
+  /// 在回调返回后删除该元素及其子孙节点
+  #[napi]
+  pub fn remove(&self) -> napi::Result<()> {
+    self.scope.check()?;
+    let mut removed = self.scope.removed.borrow_mut();
+    if !removed.contains(&self.path) {
+      removed.push(self.path.clone());
+    }
+    Ok(())
+  }
+}
+
+/// 传给 `root.enter`/`root.exit` 回调的文档根节点
+#[napi]
+pub struct XastRoot {
+  scope: Rc<ViewScope>,
+}
+
+#[napi]
+impl XastRoot {
+  /// 顶层元素，通常只有根 `<svg>`
+  #[napi(getter)]
+  pub fn children(&self) -> napi::Result<Vec<XastElement>> {
+    let indices = self.scope.root_children()?;
+    Ok(
+      indices
+        .into_iter()
+        .map(|index| XastElement {
+          path: vec![index],
+          scope: self.scope.clone(),
+        })
+        .collect(),
+    )
+  }
+}
+
+fn collect_text(children: &[XMLAstChild], text: &mut String) {
+  for child in children {
+    match child {
+      XMLAstChild::Text(t) => text.push_str(t.value),
+      XMLAstChild::Cdata(cd) => text.push_str(cd.value),
+      XMLAstChild::Element(el) => collect_text(&el.children, text),
+      _ => {}
+    }
+  }
+}
+
+/// 删除回调中标记过的元素，`children` 是路径中第一个下标所在的列表。
+/// 按路径从大到小删除，前面的下标不受影响，子孙也总在祖先之前删除
+fn remove_marked<'a>(children: &mut BumpVec<'a, XMLAstChild<'a>>, mut removed: Vec<Vec<usize>>) {
+  removed.sort_unstable_by(|a, b| b.cmp(a));
+  for path in removed {
+    let Some((&last, parents)) = path.split_last() else {
+      continue;
+    };
+    let mut children = &mut *children;
+    for &index in parents {
+      children = match &mut children[index] {
+        XMLAstChild::Element(el) => &mut el.children,
+        _ => unreachable!("view paths only point at elements"),
+      };
+    }
+    children.remove(last);
+  }
+}
+
+/// 自定义插件的回调
+pub struct JsPluginCallbacks<'a> {
+  pub element_enter: Option<Function<'a, XastElement, ()>>,
+  pub element_exit: Option<Function<'a, XastElement, ()>>,
+  pub root_enter: Option<Function<'a, XastRoot, ()>>,
+  pub root_exit: Option<Function<'a, XastRoot, ()>>,
+}
+
+/// 调用 JS 回调的插件，回调抛出的异常记录在 `callback_error` 中，之后不再调用任何回调
+pub struct JsPlugin<'a> {
+  name: String,
+  callbacks: JsPluginCallbacks<'a>,
+  callback_error: Rc<RefCell<Option<napi::Error>>>,
+  arena: &'a Bump,
+}
+
+impl<'a> JsPlugin<'a> {
+  pub fn new(
+    name: &str,
+    callbacks: JsPluginCallbacks<'a>,
+    callback_error: Rc<RefCell<Option<napi::Error>>>,
+    arena: &'a Bump,
+  ) -> Self {
+    JsPlugin {
+      name: name.to_string(),
+      callbacks,
+      callback_error,
+      arena,
+    }
+  }
+
+  /// 调用回调并返回被标记删除的元素的路径；出错时返回 `None`
+  fn call<T: JsValuesTupleIntoVec>(
+    &self,
+    callback: &Function<'a, T, ()>,
+    base: Base,
+    view: impl FnOnce(&Rc<ViewScope>) -> T,
+  ) -> Option<Vec<Vec<usize>>> {
+    if self.callback_error.borrow().is_some() {
+      return None;
+    }
+    let scope = ViewScope::new(self.arena, base);
+    let result = callback.call(view(&scope));
+    scope.alive.set(false);
+    match result {
+      Ok(()) => Some(scope.removed.take()),
+      Err(e) => {
+        self.fail(e);
+        None
+      }
+    }
+  }
+
+  fn fail(&self, e: napi::Error) {
+    let e = napi::Error::from_reason(format!("{}: {}", self.name, e.reason));
+    self.callback_error.borrow_mut().get_or_insert(e);
+  }
+
+  fn call_root(&self, callback: &Option<Function<'a, XastRoot, ()>>, root: &mut XMLAstRoot<'a>) {
+    let Some(callback) = callback else {
+      return;
+    };
+    let base = Base::Root((root as *mut XMLAstRoot).cast());
+    let view = |scope: &Rc<ViewScope>| XastRoot {
+      scope: scope.clone(),
+    };
+    if let Some(removed) = self.call(callback, base, view) {
+      remove_marked(&mut root.children, removed);
+    }
+  }
+}
+
+impl<'a> Plugin<'a> for JsPlugin<'a> {
+  fn root_enter(&mut self, root: &mut XMLAstRoot<'a>) {
+    self.call_root(&self.callbacks.root_enter, root);
+  }
+
+  fn root_exit(&mut self, root: &mut XMLAstRoot<'a>) {
+    self.call_root(&self.callbacks.root_exit, root);
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    let Some(callback) = &self.callbacks.element_enter else {
+      return VisitAction::Keep;
+    };
+    let Some(removed) = self.call(callback, element_base(el), element_view) else {
+      return VisitAction::Keep;
+    };
+    if removed.iter().any(Vec::is_empty) {
+      return VisitAction::Remove;
+    }
+    remove_marked(&mut el.children, removed);
+    VisitAction::Keep
+  }
+
+  fn element_exit(&mut self, el: &mut XMLAstElement<'a>, _ctx: &VisitContext<'_, 'a>) {
+    let Some(callback) = &self.callbacks.element_exit else {
+      return;
+    };
+    let Some(removed) = self.call(callback, element_base(el), element_view) else {
+      return;
+    };
+    if removed.iter().any(Vec::is_empty) {
+      self.fail(napi::Error::from_reason(
+        "an element can only remove itself in element.enter",
+      ));
+      return;
+    }
+    remove_marked(&mut el.children, removed);
+  }
+}
+
+fn element_base(el: &mut XMLAstElement) -> Base {
+  Base::Element((el as *mut XMLAstElement).cast())
+}
+
+fn element_view(scope: &Rc<ViewScope>) -> XastElement {
+  XastElement {
+    path: Vec::new(),
+    scope: scope.clone(),
+  }
+}
@@ -1,64 +1,313 @@
-mod optimizer;
-mod parser;
-mod plugins;
+//! 用 Rust 实现的 SVG 优化器，插件与 svgo 对应。
+//!
+//! ```no_run
+//! use svgo_rs::{optimize, Config, PluginConfig};
+//!
+//! let config = Config {
+//!   plugins: Some(vec![PluginConfig::new("preset-default", None)]),
+//!   ..Default::default()
+//! };
+//! let output = optimize(r#"<svg><!-- Example --><path d="M0 0"/></svg>"#, &config).unwrap();
+//! assert_eq!(output.data, r#"<svg><path d="M0 0"/></svg>"#);
+//! ```
+//!
+//! 需要自定义插件时，用 [`parse_svg`] 解析后把插件交给 [`SvgOptimizer`]。
+//! Node.js 绑定由默认开启的 `napi` feature 提供，只使用 Rust API 时可以关闭。
+#[cfg(feature = "napi")]
+#[cfg_attr(test, allow(dead_code))]
+mod binding;
+mod config;
+mod datauri;
+#[cfg(feature = "napi")]
+#[cfg_attr(test, allow(dead_code))]
+mod js_plugin;
+pub mod optimizer;
+pub mod parser;
+mod path_data;
+pub mod plugins;
+mod transform;
+
+use std::fmt;
 
 use bumpalo::Bump;
-use napi_derive::napi;
-use optimizer::SvgOptimizer;
-use parser::parse_svg;
-use plugins::move_elems_attrs_to_group::{
-  MoveElemsAttrsToGroupPlugin, MoveElemsAttrsToGroupPluginConfig,
+
+pub use config::{find_config_file, CONFIG_FILES};
+pub use datauri::DataUri;
+pub use optimizer::{
+  Ancestor, NamedPlugin, Plugin, PluginStats, SvgOptimizer, TraversalMode, VisitAction,
+  VisitContext,
+};
+pub use parser::{parse_svg, XMLAstChild, XMLAstElement, XMLAstRoot};
+pub use plugins::registry::{
+  create_plugins, default_plugins, skipped_plugins, PluginConfig, PluginContext,
 };
-use plugins::remove_comments::{RemoveCommentsConfig, RemoveCommentsPlugin};
-use plugins::remove_desc::{RemoveDescPlugin, RemoveDescPluginConfig};
-use plugins::remove_doctype::{RemoveDoctypePlugin, RemoveDoctypePluginConfig};
-use plugins::remove_editors_ns_data::{RemoveEditorsNSData, RemoveEditorsNSDataConfig};
-use plugins::remove_metadata::{RemoveMetadataPlugin, RemoveMetadataPluginConfig};
-use plugins::remove_title::{RemoveTitlePlugin, RemoveTitlePluginConfig};
-use plugins::remove_xml_proc_inst::{RemoveXMLProcInstPlugin, RemoveXMLProcInstPluginConfig};
-
-#[napi]
-pub fn optimize(input_xml: String) -> String {
-  // 只有在 debug build 时才初始化 env_logger
-  if cfg!(debug_assertions) {
-    let _ = env_logger::try_init();
+
+/// 多次优化时最多执行的次数，与 svgo 相同
+const MULTIPASS_LIMIT: usize = 10;
+
+/// [`optimize`] 的配置
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+  /// 被优化文件的路径，`prefixIds` 用它生成默认前缀
+  pub path: Option<String>,
+  /// svgo 格式的插件列表，可以使用 `preset-default`，为 `None` 时使用 [`default_plugins`]
+  pub plugins: Option<Vec<PluginConfig>>,
+  /// 重复优化直到结果不再变小，最多 10 次
+  pub multipass: bool,
+  /// 小数部分保留的位数，优先于插件参数中的 `floatPrecision`
+  pub float_precision: Option<u32>,
+  /// 格式化输出，每个元素单独一行
+  pub pretty: bool,
+  /// 格式化输出时的缩进空格数，默认 4
+  pub indent: Option<usize>,
+  /// 统计每个插件删除、修改的节点数和耗时，会让优化变慢
+  pub stats: bool,
+  pub traversal: TraversalMode,
+  /// 把结果输出为 data URI，多次优化时只在最后一次之后编码
+  pub datauri: Option<DataUri>,
+}
+
+/// [`optimize`] 的结果
+#[derive(Debug, Clone)]
+pub struct Output {
+  /// 优化后的 SVG，设置了 `datauri` 时是编码后的 data URI
+  pub data: String,
+  /// 每个插件的统计信息，按插件执行顺序排列，只有开启 `stats` 时才有
+  pub stats: Option<Vec<PluginStats>>,
+  /// 输入的字节数
+  pub original_size: usize,
+  /// 输出的字节数，不包括 data URI 编码带来的变化
+  pub optimized_size: usize,
+  /// 配置中请求了、但尚未实现而被跳过的 svgo 插件，见 [`skipped_plugins`]
+  pub skipped_plugins: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+  /// 输入不是合法的 XML
+  Parse(String),
+  /// 插件列表有误，如未知的插件名或错误的参数
+  Config(String),
+}
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::Parse(message) | Error::Config(message) => f.write_str(message),
+    }
   }
+}
+
+impl std::error::Error for Error {}
+
+/// 按配置中的插件列表优化 SVG
+pub fn optimize(input: &str, config: &Config) -> Result<Output, Error> {
+  let mut output = optimize_once(input, config)?;
+  if config.multipass {
+    for _ in 1..MULTIPASS_LIMIT {
+      let next = optimize_once(&output.data, config)?;
+      if next.optimized_size >= output.optimized_size {
+        break;
+      }
+      if let (Some(stats), Some(next)) = (&mut output.stats, next.stats) {
+        for (stats, next) in stats.iter_mut().zip(next) {
+          stats.removed_nodes += next.removed_nodes;
+          stats.modified_nodes += next.modified_nodes;
+          stats.elapsed += next.elapsed;
+        }
+      }
+      output.data = next.data;
+      output.optimized_size = next.optimized_size;
+    }
+  }
+  if let Some(datauri) = config.datauri {
+    output.data = datauri.encode(&output.data);
+  }
+  if let Some(plugins) = &config.plugins {
+    output.skipped_plugins = skipped_plugins(plugins);
+  }
+  Ok(output)
+}
+
+fn optimize_once(input: &str, config: &Config) -> Result<Output, Error> {
   let arena = Bump::new();
-  let mut root = parse_svg(&input_xml, &arena).unwrap();
-  let mut optimizer = SvgOptimizer::new(vec![
-    Box::new(RemoveDescPlugin::new(
-      RemoveDescPluginConfig { remove_any: true },
-      &arena,
-    )),
-    Box::new(RemoveDoctypePlugin::new(
-      RemoveDoctypePluginConfig {},
-      &arena,
-    )),
-    Box::new(RemoveTitlePlugin::new(RemoveTitlePluginConfig {}, &arena)),
-    Box::new(RemoveCommentsPlugin::new(
-      RemoveCommentsConfig {
-        preserve_patterns: None,
-      },
-      &arena,
-    )),
-    Box::new(RemoveXMLProcInstPlugin::new(
-      RemoveXMLProcInstPluginConfig {},
-      &arena,
-    )),
-    Box::new(RemoveMetadataPlugin::new(
-      RemoveMetadataPluginConfig {},
-      &arena,
-    )),
-    Box::new(MoveElemsAttrsToGroupPlugin::new(
-      MoveElemsAttrsToGroupPluginConfig {},
-      &arena,
-    )),
-    Box::new(RemoveEditorsNSData::new(
-      RemoveEditorsNSDataConfig {
-        additional_namespace: None,
-      },
-      &arena,
-    )),
-  ]);
-  optimizer.optimize(&mut root)
+  let root = parse_svg(input, &arena).map_err(|e| Error::Parse(e.to_string()))?;
+  let context = PluginContext {
+    arena: &arena,
+    path: config.path.clone(),
+    float_precision: config.float_precision,
+  };
+  let plugins = match &config.plugins {
+    Some(configs) => create_plugins(configs, &context),
+    None => create_plugins(&default_plugins(), &context),
+  }
+  .map_err(Error::Config)?;
+  Ok(run(input, root, plugins, config))
+}
+
+/// 用创建好的插件优化解析好的文档，`config` 中的插件列表不会被使用
+pub(crate) fn run<'a>(
+  input: &str,
+  mut root: XMLAstRoot<'a>,
+  plugins: Vec<NamedPlugin<'a>>,
+  config: &Config,
+) -> Output {
+  let mut optimizer = SvgOptimizer::new(plugins).with_mode(config.traversal);
+  if config.stats {
+    optimizer = optimizer.with_stats();
+  }
+  if config.pretty {
+    optimizer = optimizer.with_pretty(config.indent.unwrap_or(4));
+  }
+  let data = optimizer.optimize(&mut root);
+  Output {
+    stats: optimizer.stats().map(<[PluginStats]>::to_vec),
+    original_size: input.len(),
+    optimized_size: data.len(),
+    skipped_plugins: Vec::new(),
+    data,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+
+  use super::*;
+
+  /// 第一遍删除 `#x` 后，第二遍 `moveElemsAttrsToGroup` 才能把 `fill` 移到 `<g>` 上
+  const SHRINKS_TWICE: &str =
+    r#"<svg><g><path fill="red"/><path fill="red"/><path id="x" fill="blue"/></g></svg>"#;
+
+  fn two_pass_config() -> Config {
+    Config {
+      plugins: Some(vec![
+        PluginConfig::new("moveElemsAttrsToGroup", None),
+        PluginConfig::new("removeElementsByAttr", Some(json!({ "id": "x" }))),
+      ]),
+      traversal: TraversalMode::Sequential,
+      stats: true,
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn multipass_repeats_until_the_output_stops_shrinking() {
+    let config = two_pass_config();
+    let once = optimize(SHRINKS_TWICE, &config).unwrap();
+    assert_eq!(
+      once.data,
+      r#"<svg><g><path fill="red"/><path fill="red"/></g></svg>"#
+    );
+
+    let config = Config {
+      multipass: true,
+      ..config
+    };
+    let output = optimize(SHRINKS_TWICE, &config).unwrap();
+    assert_eq!(
+      output.data,
+      r#"<svg><g fill="red"><path/><path/></g></svg>"#
+    );
+    assert_eq!(output.original_size, SHRINKS_TWICE.len());
+    assert_eq!(output.optimized_size, output.data.len());
+    // 已经不能再优化的输入只执行一遍，结果不变
+    assert_eq!(optimize(&output.data, &config).unwrap().data, output.data);
+  }
+
+  #[test]
+  fn multipass_merges_stats_of_every_pass() {
+    let config = Config {
+      multipass: true,
+      ..two_pass_config()
+    };
+    let stats = optimize(SHRINKS_TWICE, &config).unwrap().stats.unwrap();
+    let names: Vec<_> = stats.iter().map(|stats| stats.name.as_str()).collect();
+    assert_eq!(names, ["moveElemsAttrsToGroup", "removeElementsByAttr"]);
+    // 第一遍什么都没移动，修改来自第二遍；删除只发生在第一遍，没有增大的那一遍不计入
+    assert!(stats[0].modified_nodes > 0);
+    assert_eq!(stats[1].removed_nodes, 1);
+
+    let once = optimize(SHRINKS_TWICE, &two_pass_config()).unwrap().stats.unwrap();
+    assert_eq!(once[0].modified_nodes, 0);
+    assert!(optimize(SHRINKS_TWICE, &Config::default()).unwrap().stats.is_none());
+  }
+
+  #[test]
+  fn datauri_is_applied_after_the_last_pass() {
+    let config = Config {
+      datauri: Some(DataUri::Unenc),
+      multipass: true,
+      ..two_pass_config()
+    };
+    let output = optimize(SHRINKS_TWICE, &config).unwrap();
+    assert_eq!(
+      output.data,
+      r#"data:image/svg+xml,<svg><g fill="red"><path/><path/></g></svg>"#
+    );
+    assert_eq!(
+      output.optimized_size,
+      output.data.len() - "data:image/svg+xml,".len()
+    );
+  }
+
+  #[test]
+  fn errors_distinguish_input_from_config() {
+    assert!(matches!(
+      optimize("<svg></g>", &Config::default()),
+      Err(Error::Parse(_))
+    ));
+    let config = |plugin: PluginConfig| Config {
+      plugins: Some(vec![plugin]),
+      ..Default::default()
+    };
+    let unknown = optimize("<svg/>", &config(PluginConfig::new("removeDescc", None)));
+    assert!(matches!(unknown, Err(Error::Config(message)) if message.contains("removeDesc")));
+    let bad_param = PluginConfig::new("removeDesc", Some(json!({ "removeAny": 1 })));
+    assert!(matches!(
+      optimize("<svg/>", &config(bad_param)),
+      Err(Error::Config(_))
+    ));
+  }
+
+  #[test]
+  fn skipped_plugins_are_reported() {
+    let preset = PluginConfig::new(
+      "preset-default",
+      Some(
+        json!({ "overrides": { "cleanupIds": false, "convertColors": {}, "removeDesc": false } }),
+      ),
+    );
+    let config = Config {
+      plugins: Some(vec![
+        PluginConfig::new("mergePaths", None),
+        PluginConfig::new("removeComments", None),
+        preset,
+        PluginConfig::new("mergePaths", None),
+      ]),
+      ..Default::default()
+    };
+    let output = optimize("<svg><!--c--></svg>", &config).unwrap();
+    assert_eq!(output.data, "<svg/>");
+    assert_eq!(output.skipped_plugins, ["mergePaths", "convertColors"]);
+    assert_eq!(config.validate().unwrap(), output.skipped_plugins);
+
+    let output = optimize("<svg/>", &Config::default()).unwrap();
+    assert!(output.skipped_plugins.is_empty());
+  }
+
+  #[test]
+  fn plugins_default_to_the_default_list() {
+    let input = r#"<?xml version="1.0"?><!DOCTYPE svg><svg><!-- c --><title>t</title><desc>d</desc><metadata/><g><path fill="red"/><path fill="red"/></g></svg>"#;
+    let expected = r#"<svg><g fill="red"><path/><path/></g></svg>"#;
+    let config = Config {
+      stats: true,
+      ..Default::default()
+    };
+    let output = optimize(input, &config).unwrap();
+    assert_eq!(output.data, expected);
+    let names: Vec<_> = output.stats.unwrap().into_iter().map(|stats| stats.name).collect();
+    let defaults: Vec<_> = default_plugins().into_iter().map(|plugin| plugin.name).collect();
+    assert_eq!(names, defaults);
+  }
 }
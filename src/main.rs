@@ -0,0 +1,834 @@
+//! svgo-rs 命令行，参数与 svgo 的 CLI 兼容
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use std::{env, fs};
+
+use regex::Regex;
+use svgo_rs::plugins::registry::BUILTIN_PLUGINS;
+use svgo_rs::{find_config_file, optimize, skipped_plugins, Config, DataUri, PluginConfig};
+
+const HELP: &str = "Usage: svgo-rs [options] [INPUT...]
+
+Nodejs-free SVG Optimizer, compatible with the svgo command line
+
+Arguments:
+  INPUT                      Alias to --input
+
+Options:
+  -i, --input <INPUT...>     Input files, globs or \"-\" for STDIN
+  -s, --string <STRING>      Input SVG data string
+  -f, --folder <FOLDER>      Input folder, optimize and rewrite all *.svg files
+  -o, --output <OUTPUT...>   Output file or folder (by default the same as the input), \"-\" for STDOUT
+  -p, --precision <INTEGER>  Set number of digits in the fractional part, overrides plugins params
+      --config <CONFIG>      Custom config file, .json or .toml (by default the nearest svgo.config.json
+                             or svgo-rs.toml above each input)
+      --datauri <FORMAT>     Output as Data URI string (base64, URI encoded (enc) or unencoded (unenc))
+      --multipass            Pass over SVGs multiple times to ensure all optimizations are applied
+      --pretty               Make SVG pretty printed
+      --indent <INTEGER>     Indent number when pretty printing SVGs
+  -r, --recursive            Use with '--folder'. Optimizes *.svg files in folders recursively.
+  -q, --quiet                Only output error messages, not regular status messages
+      --show-plugins         Show available plugins and exit
+  -v, --version              Output the version number
+  -h, --help                 Display help for command
+";
+
+#[derive(Default)]
+struct Args {
+  inputs: Vec<String>,
+  string: Option<String>,
+  folder: Option<PathBuf>,
+  outputs: Vec<String>,
+  precision: Option<u32>,
+  config: Option<PathBuf>,
+  datauri: Option<DataUri>,
+  multipass: bool,
+  pretty: bool,
+  indent: Option<usize>,
+  recursive: bool,
+  quiet: bool,
+  show_plugins: bool,
+  help: bool,
+  version: bool,
+}
+
+impl Args {
+  fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args::default();
+    let mut args = args.into_iter().peekable();
+    while let Some(arg) = args.next() {
+      // `--name=value` 与 `--name value` 等价
+      let (name, mut inline) = match arg.split_once('=') {
+        Some((name, value)) if name.starts_with("--") => {
+          (name.to_string(), Some(value.to_string()))
+        }
+        _ => (arg.clone(), None),
+      };
+      let mut value = |name: &str| {
+        inline
+          .take()
+          .or_else(|| args.next())
+          .ok_or_else(|| format!("option '{}' argument missing", name))
+      };
+      match name.as_str() {
+        "-i" | "--input" | "-o" | "--output" => {
+          let mut values = vec![value(&name)?];
+          // 与 svgo 相同，可以跟多个值，直到下一个选项
+          while let Some(next) = args.next_if(|next| next == "-" || !next.starts_with('-')) {
+            values.push(next);
+          }
+          if name == "-i" || name == "--input" {
+            parsed.inputs.extend(values);
+          } else {
+            parsed.outputs.extend(values);
+          }
+        }
+        "-s" | "--string" => parsed.string = Some(value(&name)?),
+        "-f" | "--folder" => parsed.folder = Some(PathBuf::from(value(&name)?)),
+        "-p" | "--precision" => parsed.precision = Some(number(&name, &value(&name)?)?),
+        "--config" => parsed.config = Some(PathBuf::from(value(&name)?)),
+        "--indent" => parsed.indent = Some(number(&name, &value(&name)?)?),
+        "--datauri" => parsed.datauri = Some(value(&name)?.parse()?),
+        "--multipass" => parsed.multipass = true,
+        "--pretty" => parsed.pretty = true,
+        "-r" | "--recursive" => parsed.recursive = true,
+        "-q" | "--quiet" => parsed.quiet = true,
+        "--show-plugins" => parsed.show_plugins = true,
+        "-h" | "--help" => parsed.help = true,
+        "-v" | "--version" => parsed.version = true,
+        "-" => parsed.inputs.push(arg),
+        _ if name.starts_with('-') => return Err(format!("unknown option '{}'", name)),
+        _ => parsed.inputs.push(arg),
+      }
+    }
+    Ok(parsed)
+  }
+}
+
+fn number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+  value.parse().map_err(|_| {
+    format!(
+      "option '{}' expects a non-negative integer, got \"{}\"",
+      name, value
+    )
+  })
+}
+
+#[derive(Debug, PartialEq)]
+enum Source {
+  Stdin,
+  String(String),
+  File(PathBuf),
+}
+
+#[derive(Debug, PartialEq)]
+enum Destination {
+  Stdout,
+  File(PathBuf),
+}
+
+#[derive(Debug, PartialEq)]
+struct Job {
+  source: Source,
+  destination: Destination,
+}
+
+/// 单个输入的处理结果，由主线程按输入顺序输出
+enum Processed {
+  /// 要写到标准输出的内容
+  Stdout(String),
+  File(Report),
+}
+
+/// 单个文件的优化结果，用于打印节省的大小
+struct Report {
+  name: String,
+  elapsed: Duration,
+  original_size: usize,
+  optimized_size: usize,
+}
+
+fn main() -> ExitCode {
+  match run(Args::parse(std::env::args().skip(1))) {
+    Ok(true) => ExitCode::SUCCESS,
+    Ok(false) => ExitCode::FAILURE,
+    Err(e) => {
+      eprintln!("svgo-rs: {}", e);
+      ExitCode::FAILURE
+    }
+  }
+}
+
+/// 所有文件都成功时返回 `true`，单个文件的错误直接打印
+fn run(args: Result<Args, String>) -> Result<bool, String> {
+  let mut args = args?;
+  if args.help {
+    print!("{}", HELP);
+    return Ok(true);
+  }
+  if args.version {
+    println!("{}", env!("CARGO_PKG_VERSION"));
+    return Ok(true);
+  }
+  if args.show_plugins {
+    println!("Currently available plugins:");
+    for plugin in BUILTIN_PLUGINS {
+      println!(" [ {} ] {}", plugin.name, plugin.description);
+    }
+    return Ok(true);
+  }
+  if args.inputs.is_empty() && args.string.is_none() && args.folder.is_none() {
+    // 与 svgo 相同，没有输入时从管道读取，否则打印帮助
+    if io::stdin().is_terminal() {
+      print!("{}", HELP);
+      return Ok(true);
+    }
+    args.inputs.push("-".to_string());
+  }
+
+  let mut configs = Configs {
+    args: &args,
+    cache: HashMap::new(),
+  };
+  let jobs = plan_jobs(&args)?
+    .into_iter()
+    .map(|job| Ok((configs.get(&job.source)?, job)))
+    .collect::<Result<Vec<_>, String>>()?;
+  let (streams, files): (Vec<_>, Vec<_>) =
+    jobs.into_iter().partition(|(_, job)| !matches!(job.source, Source::File(_)));
+
+  let mut ok = true;
+  for (config, job) in streams {
+    ok &= finish(process(&job, &config), args.quiet);
+  }
+  for result in process_parallel(&files) {
+    ok &= finish(result, args.quiet);
+  }
+  Ok(ok)
+}
+
+/// 每个输入使用的配置，没有 `--config` 时使用离输入最近的配置文件，同一个文件只读取一次
+struct Configs<'a> {
+  args: &'a Args,
+  cache: HashMap<Option<PathBuf>, Arc<Config>>,
+}
+
+impl Configs<'_> {
+  fn get(&mut self, source: &Source) -> Result<Arc<Config>, String> {
+    let path = match (&self.args.config, source) {
+      (Some(path), _) => Some(path.clone()),
+      (None, Source::File(file)) => {
+        let dir = file.parent().unwrap_or(Path::new(""));
+        find_config_file(&current_dir()?.join(dir))
+      }
+      // 标准输入和字符串从当前目录开始查找
+      (None, _) => find_config_file(&current_dir()?),
+    };
+    if let Some(config) = self.cache.get(&path) {
+      return Ok(config.clone());
+    }
+    let config = Arc::new(load_config(self.args, path.as_deref())?);
+    self.cache.insert(path, config.clone());
+    Ok(config)
+  }
+}
+
+fn current_dir() -> Result<PathBuf, String> {
+  env::current_dir().map_err(|e| format!("current directory: {}", e))
+}
+
+fn load_config(args: &Args, path: Option<&Path>) -> Result<Config, String> {
+  let mut config = match path {
+    Some(path) => Config::load(path).map_err(|e| e.to_string())?,
+    None => Config::default(),
+  };
+  // svgo 的 CLI 默认使用 preset-default
+  let plugins = config
+    .plugins
+    .get_or_insert_with(|| vec![PluginConfig::new("preset-default", None)]);
+  if !args.quiet {
+    for name in skipped_plugins(plugins) {
+      eprintln!("{}", skipped_warning(path, &name));
+    }
+  }
+  if args.datauri.is_some() {
+    config.datauri = args.datauri;
+  }
+  config.multipass |= args.multipass;
+  config.pretty |= args.pretty;
+  if args.precision.is_some() {
+    config.float_precision = args.precision;
+  }
+  if args.indent.is_some() {
+    config.indent = args.indent;
+  }
+  Ok(config)
+}
+
+/// 配置中的插件尚未实现时的提示，每个配置文件只提示一次
+fn skipped_warning(path: Option<&Path>, name: &str) -> String {
+  let prefix = path.map_or(String::new(), |path| format!("{}: ", path.display()));
+  format!(
+    "svgo-rs: {}plugin \"{}\" is not implemented yet, skipped",
+    prefix, name
+  )
+}
+
+/// 根据输入和输出参数确定每个文件写到哪里
+fn plan_jobs(args: &Args) -> Result<Vec<Job>, String> {
+  let first_output = args.outputs.first().map(String::as_str);
+  let to_stdout = first_output == Some("-");
+
+  if let Some(string) = &args.string {
+    let destination = match first_output {
+      None | Some("-") => Destination::Stdout,
+      Some(path) => Destination::File(PathBuf::from(path)),
+    };
+    return Ok(vec![Job {
+      source: Source::String(string.clone()),
+      destination,
+    }]);
+  }
+
+  let mut jobs = Vec::new();
+  // 目录中的文件保留相对路径，输出到 `-o` 指定的目录中
+  let mut folders: Vec<PathBuf> = args.folder.iter().cloned().collect();
+  let mut files = Vec::new();
+  for input in &args.inputs {
+    if input == "-" {
+      let destination = match first_output {
+        Some(path) if path != "-" => Destination::File(PathBuf::from(path)),
+        _ => Destination::Stdout,
+      };
+      jobs.push(Job {
+        source: Source::Stdin,
+        destination,
+      });
+    } else if is_glob(input) {
+      let matches = expand_glob(input)?;
+      if matches.is_empty() {
+        return Err(format!("no such file or directory: {}", input));
+      }
+      files.extend(matches);
+    } else if Path::new(input).is_dir() {
+      folders.push(PathBuf::from(input));
+    } else {
+      files.push(PathBuf::from(input));
+    }
+  }
+
+  if !files.is_empty() {
+    let destinations: Vec<Destination> = match args.outputs.as_slice() {
+      _ if to_stdout => files.iter().map(|_| Destination::Stdout).collect(),
+      [] => files.iter().map(|file| Destination::File(file.clone())).collect(),
+      [output] if files.len() == 1 && !Path::new(output).is_dir() => {
+        vec![Destination::File(PathBuf::from(output))]
+      }
+      // 一个输出对应多个输入时把它当作目录
+      [output] => files
+        .iter()
+        .map(|file| Destination::File(Path::new(output).join(file.file_name().unwrap_or_default())))
+        .collect(),
+      outputs if outputs.len() == files.len() => {
+        outputs.iter().map(|output| Destination::File(PathBuf::from(output))).collect()
+      }
+      _ => return Err("number of outputs does not match the number of inputs".to_string()),
+    };
+    for (file, destination) in files.into_iter().zip(destinations) {
+      jobs.push(Job {
+        source: Source::File(file),
+        destination,
+      });
+    }
+  }
+
+  for folder in folders {
+    let mut svgs = Vec::new();
+    collect_svgs(&folder, args.recursive, &mut svgs)
+      .map_err(|e| format!("{}: {}", folder.display(), e))?;
+    for file in svgs {
+      let destination = match first_output {
+        Some("-") => Destination::Stdout,
+        Some(output) => {
+          let relative = file.strip_prefix(&folder).unwrap_or(&file);
+          Destination::File(Path::new(output).join(relative))
+        }
+        None => Destination::File(file.clone()),
+      };
+      jobs.push(Job {
+        source: Source::File(file),
+        destination,
+      });
+    }
+  }
+  Ok(jobs)
+}
+
+fn collect_svgs(dir: &Path, recursive: bool, svgs: &mut Vec<PathBuf>) -> io::Result<()> {
+  let mut entries = fs::read_dir(dir)?.collect::<io::Result<Vec<_>>>()?;
+  entries.sort_by_key(|entry| entry.file_name());
+  for entry in entries {
+    let path = entry.path();
+    if path.is_dir() {
+      if recursive {
+        collect_svgs(&path, recursive, svgs)?;
+      }
+    } else if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("svg")) {
+      svgs.push(path);
+    }
+  }
+  Ok(())
+}
+
+fn is_glob(input: &str) -> bool {
+  input.contains(['*', '?', '['])
+}
+
+/// 展开 shell 没有展开的 glob，支持 `*`、`?`、`[...]` 和跨目录的 `**`
+fn expand_glob(pattern: &str) -> Result<Vec<PathBuf>, String> {
+  let components: Vec<&str> = pattern.split('/').collect();
+  let literal = components.iter().take_while(|c| !is_glob(c)).count();
+  let base = match components[..literal].join("/") {
+    base if base.is_empty() && literal > 0 => PathBuf::from("/"),
+    base => PathBuf::from(base),
+  };
+  let mut patterns = Vec::new();
+  for component in &components[literal..] {
+    patterns.push(match *component {
+      "**" => None,
+      component => Some(glob_regex(component)?),
+    });
+  }
+  let mut matches = Vec::new();
+  walk_glob(&base, &patterns, &mut matches);
+  matches.sort();
+  matches.dedup();
+  Ok(matches)
+}
+
+fn walk_glob(dir: &Path, patterns: &[Option<Regex>], matches: &mut Vec<PathBuf>) {
+  let Some((first, rest)) = patterns.split_first() else {
+    if dir.is_file() {
+      matches.push(dir.to_path_buf());
+    }
+    return;
+  };
+  let read_from = if dir.as_os_str().is_empty() {
+    Path::new(".")
+  } else {
+    dir
+  };
+  let Ok(entries) = fs::read_dir(read_from) else {
+    return;
+  };
+  if first.is_none() {
+    // `**` 匹配零个或多个目录
+    walk_glob(dir, rest, matches);
+  }
+  for entry in entries.flatten() {
+    let name = entry.file_name();
+    let name = name.to_string_lossy();
+    if name.starts_with('.') {
+      continue;
+    }
+    let path = dir.join(&*name);
+    match first {
+      None if path.is_dir() => walk_glob(&path, patterns, matches),
+      None => {}
+      Some(regex) if regex.is_match(&name) => walk_glob(&path, rest, matches),
+      Some(_) => {}
+    }
+  }
+}
+
+fn glob_regex(component: &str) -> Result<Regex, String> {
+  let mut regex = String::from("^");
+  let mut chars = component.chars();
+  while let Some(c) = chars.next() {
+    match c {
+      '*' => regex.push_str(".*"),
+      '?' => regex.push('.'),
+      '[' => {
+        regex.push('[');
+        let mut class: String = chars.by_ref().take_while(|c| *c != ']').collect();
+        if let Some(rest) = class.strip_prefix('!') {
+          class = format!("^{}", rest);
+        }
+        regex.push_str(&class.replace('\\', "\\\\"));
+        regex.push(']');
+      }
+      c => regex.push_str(&regex::escape(&c.to_string())),
+    }
+  }
+  regex.push('$');
+  Regex::new(&regex).map_err(|_| format!("invalid glob pattern: {}", component))
+}
+
+/// 用所有 CPU 核心处理文件，结果按输入顺序返回
+fn process_parallel(jobs: &[(Arc<Config>, Job)]) -> Vec<Result<Processed, String>> {
+  let workers = thread::available_parallelism().map_or(1, |n| n.get()).min(jobs.len());
+  let next = AtomicUsize::new(0);
+  let results = Mutex::new((0..jobs.len()).map(|_| None).collect::<Vec<_>>());
+  thread::scope(|scope| {
+    for _ in 0..workers {
+      scope.spawn(|| loop {
+        let i = next.fetch_add(1, Ordering::Relaxed);
+        let Some((config, job)) = jobs.get(i) else {
+          break;
+        };
+        let result = process(job, config);
+        results.lock().unwrap()[i] = Some(result);
+      });
+    }
+  });
+  results.into_inner().unwrap().into_iter().flatten().collect()
+}
+
+/// 优化单个输入，写到标准输出的内容返回给调用方，避免多个线程的输出交错
+fn process(job: &Job, config: &Config) -> Result<Processed, String> {
+  let start = Instant::now();
+  let (name, input) = match &job.source {
+    Source::Stdin => {
+      let mut input = String::new();
+      io::stdin().read_to_string(&mut input).map_err(|e| format!("stdin: {}", e))?;
+      ("stdin".to_string(), input)
+    }
+    Source::String(input) => ("string".to_string(), input.clone()),
+    Source::File(path) => {
+      let input = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+      (path.display().to_string(), input)
+    }
+  };
+  let mut config = config.clone();
+  if let Source::File(path) = &job.source {
+    config.path = Some(path.display().to_string());
+  }
+  let output = optimize(&input, &config).map_err(|e| format!("{}: {}", name, e))?;
+  match &job.destination {
+    Destination::Stdout => Ok(Processed::Stdout(output.data)),
+    Destination::File(path) => {
+      if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| format!("{}: {}", parent.display(), e))?;
+      }
+      fs::write(path, &output.data).map_err(|e| format!("{}: {}", path.display(), e))?;
+      // 使用 datauri 时写入的内容比优化后的 SVG 大，按实际写入的大小计算
+      Ok(Processed::File(Report {
+        name,
+        elapsed: start.elapsed(),
+        original_size: output.original_size,
+        optimized_size: output.data.len(),
+      }))
+    }
+  }
+}
+
+/// 输出单个输入的结果，返回是否成功
+fn finish(result: Result<Processed, String>, quiet: bool) -> bool {
+  match result {
+    Ok(Processed::Stdout(data)) => {
+      // 格式化输出已经以换行结尾
+      let newline = if data.ends_with('\n') { "" } else { "\n" };
+      match write!(io::stdout().lock(), "{}{}", data, newline) {
+        Ok(()) => true,
+        Err(e) => {
+          eprintln!("svgo-rs: stdout: {}", e);
+          false
+        }
+      }
+    }
+    Ok(Processed::File(report)) => {
+      if !quiet {
+        print!("{}", format_report(&report));
+      }
+      true
+    }
+    Err(e) => {
+      eprintln!("svgo-rs: {}", e);
+      false
+    }
+  }
+}
+
+/// svgo 格式的节省信息
+fn format_report(report: &Report) -> String {
+  let profit = if report.original_size == 0 {
+    0.0
+  } else {
+    100.0 - report.optimized_size as f64 * 100.0 / report.original_size as f64
+  };
+  format!(
+    "\n{}:\nDone in {} ms!\n{} KiB {} {}% = {} KiB\n",
+    report.name,
+    report.elapsed.as_millis(),
+    kib(report.original_size),
+    if profit < 0.0 { "+" } else { "-" },
+    (profit.abs() * 10.0).round() / 10.0,
+    kib(report.optimized_size)
+  )
+}
+
+fn kib(bytes: usize) -> f64 {
+  (bytes as f64 / 1024.0 * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+  use std::process;
+
+  use super::*;
+
+  /// 测试结束时删除的临时目录
+  struct TempDir(PathBuf);
+
+  impl TempDir {
+    fn new(name: &str, files: &[&str]) -> Self {
+      let dir = env::temp_dir().join(format!("svgo-rs-cli-{}-{}", name, process::id()));
+      let _ = fs::remove_dir_all(&dir);
+      for file in files {
+        let path = dir.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "<svg><!-- c --></svg>").unwrap();
+      }
+      TempDir(dir)
+    }
+
+    fn path(&self, path: &str) -> String {
+      self.0.join(path).display().to_string()
+    }
+  }
+
+  impl Drop for TempDir {
+    fn drop(&mut self) {
+      let _ = fs::remove_dir_all(&self.0);
+    }
+  }
+
+  fn parse(args: &[&str]) -> Result<Args, String> {
+    Args::parse(args.iter().map(|arg| arg.to_string()))
+  }
+
+  fn file_job(source: &str, destination: &str) -> Job {
+    Job {
+      source: Source::File(PathBuf::from(source)),
+      destination: Destination::File(PathBuf::from(destination)),
+    }
+  }
+
+  #[test]
+  fn parse_accepts_svgo_style_options() {
+    let args = parse(&[
+      "a.svg",
+      "-i",
+      "b.svg",
+      "c.svg",
+      "--precision=2",
+      "-o",
+      "out/a.svg",
+      "-",
+      "--datauri",
+      "enc",
+      "--config=svgo-rs.toml",
+      "--multipass",
+      "-",
+    ])
+    .unwrap();
+    assert_eq!(args.inputs, ["a.svg", "b.svg", "c.svg", "-"]);
+    assert_eq!(args.outputs, ["out/a.svg", "-"]);
+    assert_eq!(args.precision, Some(2));
+    assert_eq!(args.datauri, Some(DataUri::Enc));
+    assert_eq!(args.config, Some(PathBuf::from("svgo-rs.toml")));
+    assert!(args.multipass && !args.pretty);
+  }
+
+  #[test]
+  fn parse_reports_invalid_options() {
+    let error = |args: &[&str]| parse(args).err().unwrap();
+    assert_eq!(error(&["--indent"]), "option '--indent' argument missing");
+    assert_eq!(
+      error(&["-p", "x"]),
+      "option '-p' expects a non-negative integer, got \"x\""
+    );
+    assert_eq!(error(&["--bogus"]), "unknown option '--bogus'");
+    assert!(error(&["--datauri=utf8"]).starts_with("unknown datauri \"utf8\""));
+  }
+
+  #[test]
+  fn plan_jobs_maps_inputs_to_outputs() {
+    let dir = TempDir::new("plan", &["a.svg", "b.svg"]);
+    let (a, b) = (dir.path("a.svg"), dir.path("b.svg"));
+    let plan = |args: &[&str]| plan_jobs(&parse(args).unwrap());
+
+    // 没有输出时覆盖输入
+    assert_eq!(
+      plan(&[&a, &b]).unwrap(),
+      [file_job(&a, &a), file_job(&b, &b)]
+    );
+    assert_eq!(
+      plan(&[&a, "-o", "min.svg"]).unwrap(),
+      [file_job(&a, "min.svg")]
+    );
+    // 一个输出对应多个输入时是目录
+    assert_eq!(
+      plan(&[&a, &b, "-o", "dist"]).unwrap(),
+      [file_job(&a, "dist/a.svg"), file_job(&b, "dist/b.svg")]
+    );
+    assert_eq!(
+      plan(&[&a, &b, "-o", "x.svg", "y.svg"]).unwrap(),
+      [file_job(&a, "x.svg"), file_job(&b, "y.svg")]
+    );
+    assert_eq!(
+      plan(&[&a, &b, "-o", "x.svg", "y.svg", "z.svg"]).unwrap_err(),
+      "number of outputs does not match the number of inputs"
+    );
+    let to_stdout = plan(&[&a, &b, "-o", "-"]).unwrap();
+    assert!(to_stdout.iter().all(|job| job.destination == Destination::Stdout));
+
+    let string = plan(&["-s", "<svg/>", "-o", "s.svg"]).unwrap();
+    assert_eq!(
+      string[0].destination,
+      Destination::File(PathBuf::from("s.svg"))
+    );
+    let stdin = plan(&["-"]).unwrap();
+    assert_eq!(
+      stdin,
+      [Job {
+        source: Source::Stdin,
+        destination: Destination::Stdout,
+      }]
+    );
+  }
+
+  #[test]
+  fn plan_jobs_keeps_relative_paths_of_folders() {
+    let dir = TempDir::new("folder", &["icons/a.svg", "icons/sub/b.SVG", "icons/c.txt"]);
+    let icons = dir.path("icons");
+    let plan = |args: &[&str]| plan_jobs(&parse(args).unwrap()).unwrap();
+
+    assert_eq!(
+      plan(&["-f", &icons, "-o", "dist"]),
+      [file_job(&dir.path("icons/a.svg"), "dist/a.svg")]
+    );
+    assert_eq!(
+      plan(&[&icons, "-r", "-o", "dist"]),
+      [
+        file_job(&dir.path("icons/a.svg"), "dist/a.svg"),
+        file_job(&dir.path("icons/sub/b.SVG"), "dist/sub/b.SVG"),
+      ]
+    );
+  }
+
+  #[test]
+  fn glob_regex_translates_wildcards_and_classes() {
+    let regex = glob_regex("*.svg").unwrap();
+    assert!(regex.is_match("a.svg") && !regex.is_match("a.svgz"));
+    let regex = glob_regex("icon-?[!ab].svg").unwrap();
+    assert!(regex.is_match("icon-1c.svg"));
+    assert!(!regex.is_match("icon-1a.svg") && !regex.is_match("icon-c.svg"));
+    let regex = glob_regex("[a-c]+.svg").unwrap();
+    assert!(regex.is_match("b+.svg") && !regex.is_match("bb.svg"));
+  }
+
+  #[test]
+  fn expand_glob_matches_nested_directories() {
+    let dir = TempDir::new(
+      "glob",
+      &[
+        "a.svg",
+        "sub/b.svg",
+        "sub/deep/c.svg",
+        "sub/c.txt",
+        ".hidden/d.svg",
+      ],
+    );
+    let expand = |pattern: &str| {
+      let matches = expand_glob(&dir.path(pattern)).unwrap();
+      matches
+        .iter()
+        .map(|path| path.strip_prefix(&dir.0).unwrap().display().to_string())
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(expand("**/*.svg"), ["a.svg", "sub/b.svg", "sub/deep/c.svg"]);
+    assert_eq!(expand("sub/**/c.*"), ["sub/c.txt", "sub/deep/c.svg"]);
+    assert_eq!(expand("*/b.svg"), ["sub/b.svg"]);
+    assert!(expand("*.png").is_empty());
+  }
+
+  #[test]
+  fn format_report_matches_svgo() {
+    let report = |original_size, optimized_size| {
+      format_report(&Report {
+        name: "a.svg".to_string(),
+        elapsed: Duration::from_millis(12),
+        original_size,
+        optimized_size,
+      })
+    };
+    assert_eq!(
+      report(2048, 1024),
+      "\na.svg:\nDone in 12 ms!\n2 KiB - 50% = 1 KiB\n"
+    );
+    assert_eq!(
+      report(1000, 1250),
+      "\na.svg:\nDone in 12 ms!\n0.977 KiB + 25% = 1.221 KiB\n"
+    );
+    assert_eq!(
+      report(0, 0),
+      "\na.svg:\nDone in 12 ms!\n0 KiB - 0% = 0 KiB\n"
+    );
+  }
+
+  #[test]
+  fn skipped_warning_names_the_config_file() {
+    assert_eq!(
+      skipped_warning(Some(Path::new("svgo.config.json")), "mergePaths"),
+      "svgo-rs: svgo.config.json: plugin \"mergePaths\" is not implemented yet, skipped"
+    );
+    assert_eq!(
+      skipped_warning(None, "mergePaths"),
+      "svgo-rs: plugin \"mergePaths\" is not implemented yet, skipped"
+    );
+  }
+
+  #[test]
+  fn process_reports_the_written_size_and_keeps_stdout_in_order() {
+    let dir = TempDir::new("process", &["a.svg"]);
+    let config = Config {
+      plugins: Some(vec![PluginConfig::new("removeComments", None)]),
+      datauri: Some(DataUri::Base64),
+      ..Default::default()
+    };
+    let job = file_job(&dir.path("a.svg"), &dir.path("out/a.txt"));
+    let Ok(Processed::File(report)) = process(&job, &config) else {
+      panic!("expected a report");
+    };
+    let written = fs::read_to_string(dir.0.join("out/a.txt")).unwrap();
+    assert!(written.starts_with("data:image/svg+xml;base64,"));
+    assert_eq!(report.optimized_size, written.len());
+
+    let config = Arc::new(Config::default());
+    let jobs: Vec<_> = (0..16)
+      .map(|i| {
+        let job = Job {
+          source: Source::String(format!("<svg id=\"{}\"/>", i)),
+          destination: Destination::Stdout,
+        };
+        (config.clone(), job)
+      })
+      .collect();
+    let outputs: Vec<_> = process_parallel(&jobs)
+      .into_iter()
+      .map(|result| match result {
+        Ok(Processed::Stdout(data)) => data,
+        _ => panic!("expected stdout output"),
+      })
+      .collect();
+    let expected: Vec<_> = (0..16).map(|i| format!("<svg id=\"{}\"/>", i)).collect();
+    assert_eq!(outputs, expected);
+  }
+}
@@ -3,159 +3,410 @@ use crate::parser::{
   XMLAstInstruction, XMLAstRoot, XMLAstText,
 };
 use bumpalo::collections::Vec as BumpVec;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ops::Range;
+use std::time::{Duration, Instant};
 
-#[derive(PartialEq, Eq)]
-pub enum VisitAction {
+/// enter 钩子对当前节点的处理方式
+///
+/// 替换进来的节点会从第一个插件开始重新访问，但跳过替换它的插件，插入到当前节点之后的节点
+/// 在访问完当前节点后访问，插入到当前节点之前的节点不会在这次遍历中被访问
+pub enum VisitAction<'a> {
   /// 保留该元素
   Keep,
   /// 移除该元素
   Remove,
+  /// 用元素的子节点替换该元素，其他类型的节点直接移除
+  ReplaceWithChildren,
+  /// 用另一个节点替换该节点，两个插件互相替换对方的结果时遍历不会结束
+  Replace(XMLAstChild<'a>),
+  /// 在该节点之前插入节点，后面的插件仍会访问当前节点
+  InsertBefore(Vec<XMLAstChild<'a>>),
+  /// 在该节点之后插入节点，后面的插件仍会访问当前节点
+  InsertAfter(Vec<XMLAstChild<'a>>),
 }
 
 pub trait Plugin<'a> {
-  fn root_enter(&self, _el: &mut XMLAstRoot<'a>) {}
-  fn root_exit(&self, _el: &mut XMLAstRoot<'a>) {}
+  fn root_enter(&mut self, _el: &mut XMLAstRoot<'a>) {}
+  fn root_exit(&mut self, _el: &mut XMLAstRoot<'a>) {}
 
-  /// return true 表示要把这个 element 从父节点里删掉
-  fn element_enter(&mut self, _el: &mut XMLAstElement<'a>) -> VisitAction {
+  /// 返回值决定这个节点是保留、删除还是被替换，见 [`VisitAction`]
+  fn element_enter(
+    &mut self,
+    _el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     VisitAction::Keep
   }
-  fn element_exit(&self, _el: &mut XMLAstElement<'a>) {}
+  fn element_exit(&mut self, _el: &mut XMLAstElement<'a>, _ctx: &VisitContext<'_, 'a>) {}
 
-  fn text_enter(&self, _el: &mut XMLAstText<'a>) -> VisitAction {
+  fn text_enter(
+    &mut self,
+    _el: &mut XMLAstText<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     VisitAction::Keep
   }
-  fn text_exit(&self, _el: &mut XMLAstText<'a>) {}
+  fn text_exit(&mut self, _el: &mut XMLAstText<'a>, _ctx: &VisitContext<'_, 'a>) {}
 
-  fn comment_enter(&self, _el: &mut XMLAstComment<'a>) -> VisitAction {
+  fn comment_enter(
+    &mut self,
+    _el: &mut XMLAstComment<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     VisitAction::Keep
   }
-  fn comment_exit(&self, _el: &mut XMLAstComment<'a>) {}
+  fn comment_exit(&mut self, _el: &mut XMLAstComment<'a>, _ctx: &VisitContext<'_, 'a>) {}
 
-  fn doctype_enter(&self, _el: &mut XMLAstDoctype<'a>) -> VisitAction {
+  fn doctype_enter(
+    &mut self,
+    _el: &mut XMLAstDoctype<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     VisitAction::Keep
   }
-  fn doctype_exit(&self, _el: &mut XMLAstDoctype<'a>) {}
+  fn doctype_exit(&mut self, _el: &mut XMLAstDoctype<'a>, _ctx: &VisitContext<'_, 'a>) {}
 
-  fn instruction_enter(&self, _el: &mut XMLAstInstruction<'a>) -> VisitAction {
+  fn instruction_enter(
+    &mut self,
+    _el: &mut XMLAstInstruction<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     VisitAction::Keep
   }
-  fn instruction_exit(&self, _el: &mut XMLAstInstruction<'a>) {}
+  fn instruction_exit(&mut self, _el: &mut XMLAstInstruction<'a>, _ctx: &VisitContext<'_, 'a>) {}
 
-  fn cdata_enter(&self, _el: &mut XMLAstCdata<'a>) -> VisitAction {
+  fn cdata_enter(
+    &mut self,
+    _el: &mut XMLAstCdata<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     VisitAction::Keep
   }
-  fn cdata_exit(&self, _el: &mut XMLAstCdata<'a>) {}
+  fn cdata_exit(&mut self, _el: &mut XMLAstCdata<'a>, _ctx: &VisitContext<'_, 'a>) {}
 
-  fn decl_enter(&self, _el: &mut XMLAstDecl<'a>) -> VisitAction {
+  fn decl_enter(
+    &mut self,
+    _el: &mut XMLAstDecl<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     VisitAction::Keep
   }
-  fn decl_exit(&self, _el: &mut XMLAstDecl<'a>) {}
+  fn decl_exit(&mut self, _el: &mut XMLAstDecl<'a>, _ctx: &VisitContext<'_, 'a>) {}
+
+  /// 只在 enter 钩子中读写当前节点，不依赖子孙、兄弟和祖先节点时返回 true。
+  /// 顺序遍历时相邻的这类插件会合并成一次遍历，结果与分别遍历相同
+  fn is_node_local(&self) -> bool {
+    false
+  }
+}
+
+/// 正在访问的节点的一个祖先元素，通过 `parent` 一直连到最外层的元素
+///
+/// 祖先的子节点正在被遍历，所以只能读取名字和属性
+pub struct Ancestor<'c, 'a> {
+  pub name: &'a str,
+  pub attributes: &'c [(&'a str, &'a str)],
+  parent: Option<&'c Ancestor<'c, 'a>>,
+}
+
+impl<'c, 'a> Ancestor<'c, 'a> {
+  pub fn get_attribute(&self, name: &str) -> Option<&'a str> {
+    self.attributes.iter().find(|(key, _)| *key == name).map(|(_, value)| *value)
+  }
+
+  pub fn has_attribute(&self, name: &str) -> bool {
+    self.attributes.iter().any(|(key, _)| *key == name)
+  }
+
+  pub fn parent(&self) -> Option<&'c Ancestor<'c, 'a>> {
+    self.parent
+  }
+}
+
+/// 传给节点钩子的上下文：当前节点在树中的位置和它的兄弟节点
+pub struct VisitContext<'c, 'a> {
+  parent: Option<&'c Ancestor<'c, 'a>>,
+  index: usize,
+  previous: &'c [XMLAstChild<'a>],
+  next: &'c [XMLAstChild<'a>],
+}
+
+impl<'c, 'a> VisitContext<'c, 'a> {
+  /// 父元素，顶层节点（如根 `<svg>`）返回 `None`
+  pub fn parent(&self) -> Option<&'c Ancestor<'c, 'a>> {
+    self.parent
+  }
+
+  /// 从父元素开始由近到远的所有祖先元素
+  pub fn ancestors(&self) -> impl Iterator<Item = &'c Ancestor<'c, 'a>> {
+    std::iter::successors(self.parent, |ancestor| ancestor.parent)
+  }
+
+  /// 祖先元素的个数，顶层节点为 0
+  pub fn depth(&self) -> usize {
+    self.ancestors().count()
+  }
+
+  /// 在父节点 `children` 中的位置，已经被删除的兄弟节点不计入
+  pub fn index(&self) -> usize {
+    self.index
+  }
+
+  /// 当前节点之前的兄弟节点，已经访问过
+  pub fn previous_siblings(&self) -> &'c [XMLAstChild<'a>] {
+    self.previous
+  }
+
+  /// 当前节点之后的兄弟节点，enter 钩子中还没有被访问过
+  pub fn next_siblings(&self) -> &'c [XMLAstChild<'a>] {
+    self.next
+  }
+
+  pub fn previous_sibling(&self) -> Option<&'c XMLAstChild<'a>> {
+    self.previous.last()
+  }
+
+  pub fn next_sibling(&self) -> Option<&'c XMLAstChild<'a>> {
+    self.next.first()
+  }
+}
+
+/// 插件的执行方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TraversalMode {
+  /// 所有插件共用一次遍历，每个节点依次交给所有插件处理
+  #[default]
+  Interleaved,
+  /// 与 svgo 相同，每个插件在前一个插件处理完整棵树后再遍历一次
+  Sequential,
+}
+
+/// 插件和它在配置中的名字，名字用于统计信息
+pub struct NamedPlugin<'a> {
+  pub name: String,
+  pub plugin: Box<dyn Plugin<'a> + 'a>,
+}
+
+impl<'a> NamedPlugin<'a> {
+  pub fn new(name: &str, plugin: Box<dyn Plugin<'a> + 'a>) -> Self {
+    NamedPlugin {
+      name: name.to_string(),
+      plugin,
+    }
+  }
+}
+
+/// 单个插件的统计信息
+#[derive(Debug, Clone, Default)]
+pub struct PluginStats {
+  pub name: String,
+  /// 被删除的节点数，包括被删除节点的所有子孙节点
+  pub removed_nodes: usize,
+  /// 名字、属性、内容或子节点数量发生变化的节点数，钩子修改子孙节点时只计入当前节点
+  pub modified_nodes: usize,
+  /// 插件所有钩子的耗时之和
+  pub elapsed: Duration,
 }
 
 pub struct SvgOptimizer<'a> {
-  plugins: Vec<Box<dyn Plugin<'a> + 'a>>,
+  plugins: Vec<NamedPlugin<'a>>,
+  /// 开启统计时与 `plugins` 一一对应
+  stats: Option<Vec<PluginStats>>,
+  mode: TraversalMode,
+  /// 当前这次遍历调用的插件
+  active: Range<usize>,
+  /// 格式化输出时每层缩进的空格数，`None` 时输出紧凑的 SVG
+  indent: Option<usize>,
 }
 
 impl<'a> SvgOptimizer<'a> {
-  pub fn new(plugins: Vec<Box<dyn Plugin<'a> + 'a>>) -> Self {
-    Self { plugins }
+  pub fn new(plugins: Vec<NamedPlugin<'a>>) -> Self {
+    let active = 0..plugins.len();
+    Self {
+      plugins,
+      stats: None,
+      mode: TraversalMode::default(),
+      active,
+      indent: None,
+    }
+  }
+
+  pub fn with_mode(mut self, mode: TraversalMode) -> Self {
+    self.mode = mode;
+    self
+  }
+
+  /// 每个元素单独一行并按层级缩进，含有文本的元素保持原样
+  pub fn with_pretty(mut self, indent: usize) -> Self {
+    self.indent = Some(indent);
+    self
+  }
+
+  /// 记录每个插件删除、修改的节点数和耗时，会让优化变慢
+  pub fn with_stats(mut self) -> Self {
+    let stats = self
+      .plugins
+      .iter()
+      .map(|plugin| PluginStats {
+        name: plugin.name.clone(),
+        ..Default::default()
+      })
+      .collect();
+    self.stats = Some(stats);
+    self
+  }
+
+  pub fn stats(&self) -> Option<&[PluginStats]> {
+    self.stats.as_deref()
   }
 
   pub fn optimize(&mut self, root: &mut XMLAstRoot<'a>) -> String {
-    for plugin in &self.plugins {
-      plugin.root_enter(root);
-    }
-    // 对根节点的 children 启动遍历
-    self.traverse_children(&mut root.children);
-    for plugin in &self.plugins {
-      plugin.root_exit(root);
+    let passes = match self.mode {
+      TraversalMode::Interleaved => {
+        let all = 0..self.plugins.len();
+        vec![all]
+      }
+      TraversalMode::Sequential => self.sequential_passes(),
+    };
+    for pass in passes {
+      self.active = pass;
+      self.visit_root(root, |plugin, root| plugin.root_enter(root));
+      // 对根节点的 children 启动遍历
+      self.traverse_children(&mut root.children, None);
+      self.visit_root(root, |plugin, root| plugin.root_exit(root));
     }
+    self.active = 0..self.plugins.len();
     self.generate_svg(root)
   }
 
-  fn traverse_children(&mut self, children: &mut BumpVec<'a, XMLAstChild<'a>>) {
+  /// 每个插件单独一次遍历，相邻的节点局部插件合并为一次
+  fn sequential_passes(&self) -> Vec<Range<usize>> {
+    let mut passes: Vec<Range<usize>> = Vec::new();
+    for (i, named) in self.plugins.iter().enumerate() {
+      match passes.last_mut() {
+        Some(pass)
+          if named.plugin.is_node_local() && self.plugins[pass.start].plugin.is_node_local() =>
+        {
+          pass.end = i + 1
+        }
+        _ => passes.push(i..i + 1),
+      }
+    }
+    passes
+  }
+
+  fn traverse_children(
+    &mut self,
+    children: &mut BumpVec<'a, XMLAstChild<'a>>,
+    parent: Option<&Ancestor<'_, 'a>>,
+  ) {
     let mut i = 0;
+    // 替换了当前位置节点的插件，重新访问替换进来的节点时跳过它
+    let mut replaced_by = None;
     while i < children.len() {
-      // Check if any plugin wants to remove this node via the enter hook
-      let should_remove = match &mut children[i] {
-        XMLAstChild::Doctype(el) => self
-          .plugins
-          .iter()
-          .any(|plugin| plugin.doctype_enter(el) == VisitAction::Remove),
-        XMLAstChild::Instruction(el) => self
-          .plugins
-          .iter()
-          .any(|plugin| plugin.instruction_enter(el) == VisitAction::Remove),
-        XMLAstChild::Comment(el) => self
-          .plugins
-          .iter()
-          .any(|plugin| plugin.comment_enter(el) == VisitAction::Remove),
+      let skip = replaced_by.take();
+      // Ask the plugins whether to keep, remove or replace this node
+      let (previous, rest) = children.split_at_mut(i);
+      let (child, next) = rest.split_first_mut().unwrap();
+      let ctx = VisitContext {
+        parent,
+        index: i,
+        previous,
+        next,
+      };
+      let Entered {
+        before,
+        after,
+        action,
+        source,
+      } = match child {
+        XMLAstChild::Doctype(el) => {
+          self.visit_enter(el, skip, |plugin, el| plugin.doctype_enter(el, &ctx))
+        }
+        XMLAstChild::Instruction(el) => {
+          self.visit_enter(el, skip, |plugin, el| plugin.instruction_enter(el, &ctx))
+        }
+        XMLAstChild::Comment(el) => {
+          self.visit_enter(el, skip, |plugin, el| plugin.comment_enter(el, &ctx))
+        }
         XMLAstChild::Cdata(el) => {
-          self.plugins.iter().any(|plugin| plugin.cdata_enter(el) == VisitAction::Remove)
+          self.visit_enter(el, skip, |plugin, el| plugin.cdata_enter(el, &ctx))
         }
         XMLAstChild::Text(el) => {
-          self.plugins.iter().any(|plugin| plugin.text_enter(el) == VisitAction::Remove)
+          self.visit_enter(el, skip, |plugin, el| plugin.text_enter(el, &ctx))
+        }
+        XMLAstChild::Element(el) => {
+          self.visit_enter(el, skip, |plugin, el| plugin.element_enter(el, &ctx))
         }
-        XMLAstChild::Element(el) => self
-          .plugins
-          .iter_mut()
-          .any(|plugin| plugin.element_enter(el) == VisitAction::Remove),
         // Assuming Decl nodes are never removed by plugins
         XMLAstChild::Decl(el) => {
-          self.plugins.iter().any(|plugin| plugin.decl_enter(el) == VisitAction::Remove)
+          self.visit_enter(el, skip, |plugin, el| plugin.decl_enter(el, &ctx))
         }
       };
 
-      if should_remove {
-        children.remove(i);
-        // Do not increment i, the next element shifts to the current index
-        continue;
-      }
-
-      // If not removed, traverse deeper (for elements) and call exit hooks
-      match &mut children[i] {
-        XMLAstChild::Element(el) => {
-          // Traverse children before calling exit hooks for the parent
-          self.traverse_children(&mut el.children);
-          for plugin in &self.plugins {
-            plugin.element_exit(el);
-          }
+      // 插入到前面的节点已经在当前位置之前，不再访问
+      let inserted = before.len();
+      children.splice(i..i, before);
+      i += inserted;
+      match action {
+        Some(VisitAction::Replace(node)) => {
+          children[i] = node;
+          children.splice(i + 1..i + 1, after);
+          // Do not increment i, the replacement is visited from the start
+          replaced_by = Some(source);
+          continue;
         }
-        XMLAstChild::Text(t) => {
-          // Call text_exit (enter hook was checked above)
-          for plugin in &self.plugins {
-            plugin.text_exit(t);
+        Some(action) => {
+          let removed = children.remove(i);
+          children.splice(i..i, after);
+          if let (VisitAction::ReplaceWithChildren, XMLAstChild::Element(el)) = (action, removed) {
+            children.splice(i..i, el.children);
           }
+          // Do not increment i, the next element shifts to the current index
+          continue;
         }
-        XMLAstChild::Comment(c) => {
-          // Call comment_exit
-          for plugin in &self.plugins {
-            plugin.comment_exit(c);
-          }
-        }
-        XMLAstChild::Doctype(d) => {
-          // Call doctype_exit
-          for plugin in &self.plugins {
-            plugin.doctype_exit(d);
-          }
+        None => {
+          children.splice(i + 1..i + 1, after);
         }
+      }
+
+      // Traverse children before calling exit hooks for the parent
+      if let XMLAstChild::Element(XMLAstElement {
+        name,
+        attributes,
+        children: grandchildren,
+      }) = &mut children[i]
+      {
+        let ancestor = Ancestor {
+          name,
+          attributes,
+          parent,
+        };
+        self.traverse_children(grandchildren, Some(&ancestor));
+      }
+
+      // If not removed, call exit hooks
+      let (previous, rest) = children.split_at_mut(i);
+      let (child, next) = rest.split_first_mut().unwrap();
+      let ctx = VisitContext {
+        parent,
+        index: i,
+        previous,
+        next,
+      };
+      match child {
+        XMLAstChild::Element(el) => self.visit_exit(el, |plugin, el| plugin.element_exit(el, &ctx)),
+        XMLAstChild::Text(t) => self.visit_exit(t, |plugin, t| plugin.text_exit(t, &ctx)),
+        XMLAstChild::Comment(c) => self.visit_exit(c, |plugin, c| plugin.comment_exit(c, &ctx)),
+        XMLAstChild::Doctype(d) => self.visit_exit(d, |plugin, d| plugin.doctype_exit(d, &ctx)),
         XMLAstChild::Instruction(ins) => {
-          // Call instruction_exit
-          for plugin in &self.plugins {
-            plugin.instruction_exit(ins);
-          }
-        }
-        XMLAstChild::Cdata(cd) => {
-          // Call cdata_exit
-          for plugin in &self.plugins {
-            plugin.cdata_exit(cd);
-          }
+          self.visit_exit(ins, |plugin, ins| plugin.instruction_exit(ins, &ctx))
         }
+        XMLAstChild::Cdata(cd) => self.visit_exit(cd, |plugin, cd| plugin.cdata_exit(cd, &ctx)),
         XMLAstChild::Decl(decl) => {
-          for plugin in &self.plugins {
-            plugin.decl_exit(decl);
-          }
+          self.visit_exit(decl, |plugin, decl| plugin.decl_exit(decl, &ctx))
         }
       }
       // Increment index only if the element was not removed
@@ -163,13 +414,154 @@ impl<'a> SvgOptimizer<'a> {
     }
   }
 
+  /// 依次调用 enter 钩子，有插件要求删除或替换时，后面的插件不再被调用
+  fn visit_enter<N: StatsNode>(
+    &mut self,
+    node: &mut N,
+    skip: Option<usize>,
+    enter: impl Fn(&mut dyn Plugin<'a>, &mut N) -> VisitAction<'a>,
+  ) -> Entered<'a> {
+    let mut entered = Entered {
+      before: Vec::new(),
+      after: Vec::new(),
+      action: None,
+      source: 0,
+    };
+    let active = self.active.clone();
+    let mut stats = self.stats.as_mut().map(|stats| &mut stats[active.clone()]);
+    for (i, named) in self.plugins[active.clone()].iter_mut().enumerate() {
+      if skip == Some(active.start + i) {
+        continue;
+      }
+      let stats = stats.as_deref_mut().map(|stats| &mut stats[i]);
+      let fingerprint = stats.as_ref().map(|_| node.fingerprint());
+      let start = stats.as_ref().map(|_| Instant::now());
+      let action = enter(named.plugin.as_mut(), node);
+      if let (Some(stats), Some(start)) = (stats, start) {
+        stats.elapsed += start.elapsed();
+        match &action {
+          VisitAction::Remove => stats.removed_nodes += node.node_count(),
+          VisitAction::ReplaceWithChildren => stats.removed_nodes += 1,
+          VisitAction::Keep if fingerprint == Some(node.fingerprint()) => {}
+          _ => stats.modified_nodes += 1,
+        }
+      }
+      match action {
+        VisitAction::Keep => {}
+        VisitAction::InsertBefore(nodes) => entered.before.extend(nodes),
+        VisitAction::InsertAfter(nodes) => entered.after.extend(nodes),
+        action => {
+          entered.action = Some(action);
+          entered.source = active.start + i;
+          break;
+        }
+      }
+    }
+    entered
+  }
+
+  fn visit_exit<N: StatsNode>(&mut self, node: &mut N, exit: impl Fn(&mut dyn Plugin<'a>, &mut N)) {
+    let Some(stats) = &mut self.stats else {
+      for named in &mut self.plugins[self.active.clone()] {
+        exit(named.plugin.as_mut(), node);
+      }
+      return;
+    };
+    let active = self.active.clone();
+    for (named, stats) in self.plugins[active.clone()].iter_mut().zip(&mut stats[active]) {
+      let fingerprint = node.fingerprint();
+      let start = Instant::now();
+      exit(named.plugin.as_mut(), node);
+      stats.elapsed += start.elapsed();
+      if node.fingerprint() != fingerprint {
+        stats.modified_nodes += 1;
+      }
+    }
+  }
+
+  /// root 钩子可以任意修改整棵树，统计时比较钩子前后所有节点的指纹
+  fn visit_root(
+    &mut self,
+    root: &mut XMLAstRoot<'a>,
+    hook: impl Fn(&mut dyn Plugin<'a>, &mut XMLAstRoot<'a>),
+  ) {
+    let Some(stats) = &mut self.stats else {
+      for named in &mut self.plugins[self.active.clone()] {
+        hook(named.plugin.as_mut(), root);
+      }
+      return;
+    };
+    let active = self.active.clone();
+    for (named, stats) in self.plugins[active.clone()].iter_mut().zip(&mut stats[active]) {
+      let before = tree_fingerprints(&root.children);
+      let start = Instant::now();
+      hook(named.plugin.as_mut(), root);
+      stats.elapsed += start.elapsed();
+      let after = tree_fingerprints(&root.children);
+      if before == after {
+        continue;
+      }
+      let mut unmatched: HashMap<u64, isize> = HashMap::new();
+      for fingerprint in &before {
+        *unmatched.entry(*fingerprint).or_default() += 1;
+      }
+      for fingerprint in &after {
+        *unmatched.entry(*fingerprint).or_default() -= 1;
+      }
+      // 修改过的节点在修改前后各留下一个无法匹配的指纹
+      let added = unmatched
+        .values()
+        .filter(|count| **count < 0)
+        .map(|count| -count)
+        .sum::<isize>();
+      stats.modified_nodes += added as usize;
+      stats.removed_nodes += before.len().saturating_sub(after.len());
+    }
+  }
+
   /// 根据 AST 生成 SVG 字符串
   pub fn generate_svg(&self, root: &XMLAstRoot<'a>) -> String {
     let mut output = String::new();
-    self.write_children(&root.children, &mut output);
+    match self.indent {
+      Some(indent) => self.write_pretty(&root.children, indent, 0, &mut output),
+      None => self.write_children(&root.children, &mut output),
+    }
     output
   }
 
+  fn write_pretty(
+    &self,
+    children: &BumpVec<'a, XMLAstChild<'a>>,
+    indent: usize,
+    depth: usize,
+    buf: &mut String,
+  ) {
+    use std::fmt::Write;
+    let padding = " ".repeat(indent * depth);
+    for child in children {
+      match child {
+        // 元素之间的空白由缩进代替
+        XMLAstChild::Text(t) if t.value.trim().is_empty() => {}
+        XMLAstChild::Element(el) if !el.children.is_empty() && !has_text(&el.children) => {
+          write!(buf, "{}<{}", padding, el.name).unwrap();
+          for (k, v) in &el.attributes {
+            write!(buf, " {}=\"{}\"", k, v).unwrap();
+          }
+          buf.push_str(">\n");
+          self.write_pretty(&el.children, indent, depth + 1, buf);
+          writeln!(buf, "{}</{}>", padding, el.name).unwrap();
+        }
+        XMLAstChild::Element(_) | XMLAstChild::Text(_) | XMLAstChild::Comment(_) => {
+          // 文本中的空白有意义，含有文本的元素整体原样输出
+          buf.push_str(&padding);
+          self.write_child(child, buf);
+          buf.push('\n');
+        }
+        _ => {}
+      }
+    }
+  }
+
   fn write_children(&self, children: &BumpVec<'a, XMLAstChild<'a>>, buf: &mut String) {
     for child in children {
       self.write_child(child, buf);
@@ -209,3 +601,361 @@ impl<'a> SvgOptimizer<'a> {
     }
   }
 }
+
+/// 一个节点所有 enter 钩子的结果
+struct Entered<'a> {
+  before: Vec<XMLAstChild<'a>>,
+  after: Vec<XMLAstChild<'a>>,
+  /// 删除或替换当前节点的动作，`None` 表示保留
+  action: Option<VisitAction<'a>>,
+  /// 产生 `action` 的插件在 `plugins` 中的位置
+  source: usize,
+}
+
+/// 是否有非空白的文本子节点
+fn has_text(children: &[XMLAstChild]) -> bool {
+  children.iter().any(|child| match child {
+    XMLAstChild::Text(t) => !t.value.trim().is_empty(),
+    XMLAstChild::Cdata(_) => true,
+    _ => false,
+  })
+}
+
+/// 统计时用来判断节点是否被修改
+trait StatsNode {
+  fn fingerprint(&self) -> u64;
+
+  /// 节点自身加上所有子孙节点的数量
+  fn node_count(&self) -> usize {
+    1
+  }
+}
+
+fn hash_of(value: impl Hash) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  value.hash(&mut hasher);
+  hasher.finish()
+}
+
+impl StatsNode for XMLAstElement<'_> {
+  fn fingerprint(&self) -> u64 {
+    hash_of((self.name, self.attributes.as_slice(), self.children.len()))
+  }
+
+  fn node_count(&self) -> usize {
+    1 + self.children.iter().map(StatsNode::node_count).sum::<usize>()
+  }
+}
+
+impl StatsNode for XMLAstChild<'_> {
+  fn fingerprint(&self) -> u64 {
+    match self {
+      XMLAstChild::Element(el) => el.fingerprint(),
+      XMLAstChild::Text(t) => t.fingerprint(),
+      XMLAstChild::Comment(c) => c.fingerprint(),
+      XMLAstChild::Cdata(cd) => cd.fingerprint(),
+      XMLAstChild::Doctype(d) => d.fingerprint(),
+      XMLAstChild::Instruction(ins) => ins.fingerprint(),
+      XMLAstChild::Decl(decl) => decl.fingerprint(),
+    }
+  }
+
+  fn node_count(&self) -> usize {
+    match self {
+      XMLAstChild::Element(el) => el.node_count(),
+      _ => 1,
+    }
+  }
+}
+
+macro_rules! impl_stats_node {
+  ($($node:ident => |$it:ident| $value:expr),* $(,)?) => {
+    $(impl StatsNode for $node<'_> {
+      fn fingerprint(&self) -> u64 {
+        let $it = self;
+        hash_of((stringify!($node), $value))
+      }
+    })*
+  };
+}
+
+impl_stats_node! {
+  XMLAstText => |t| t.value,
+  XMLAstComment => |c| c.value,
+  XMLAstCdata => |cd| cd.value,
+  XMLAstDecl => |decl| decl.value,
+  XMLAstInstruction => |ins| (ins.name, ins.value),
+  XMLAstDoctype => |d| (d.name, d.data.doctype),
+}
+
+/// 按先序遍历收集所有节点的指纹
+fn tree_fingerprints(children: &[XMLAstChild]) -> Vec<u64> {
+  let mut fingerprints = Vec::new();
+  let mut stack: Vec<&XMLAstChild> = children.iter().rev().collect();
+  while let Some(child) = stack.pop() {
+    fingerprints.push(child.fingerprint());
+    if let XMLAstChild::Element(el) = child {
+      stack.extend(el.children.iter().rev());
+    }
+  }
+  fingerprints
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cell::RefCell;
+  use std::rc::Rc;
+
+  use bumpalo::Bump;
+
+  use super::*;
+  use crate::parser::parse_svg;
+
+  /// 只实现 element_enter 的插件
+  struct Enter<F>(F);
+
+  impl<'a, F> Plugin<'a> for Enter<F>
+  where
+    F: FnMut(&mut XMLAstElement<'a>, &VisitContext<'_, 'a>) -> VisitAction<'a>,
+  {
+    fn element_enter(
+      &mut self,
+      el: &mut XMLAstElement<'a>,
+      ctx: &VisitContext<'_, 'a>,
+    ) -> VisitAction<'a> {
+      (self.0)(el, ctx)
+    }
+  }
+
+  fn enter<'a>(
+    hook: impl FnMut(&mut XMLAstElement<'a>, &VisitContext<'_, 'a>) -> VisitAction<'a> + 'a,
+  ) -> NamedPlugin<'a> {
+    NamedPlugin {
+      name: "enter".to_string(),
+      plugin: Box::new(Enter(hook)),
+    }
+  }
+
+  /// 把每个元素 enter 时的上下文记录为 `名字 in 祖先 #index [前面的兄弟] [后面的兄弟]`
+  fn recorder<'a>(log: &Rc<RefCell<Vec<String>>>) -> NamedPlugin<'a> {
+    let log = log.clone();
+    enter(move |el, ctx| {
+      let ancestors: Vec<_> = ctx.ancestors().map(|ancestor| ancestor.name).collect();
+      log.borrow_mut().push(format!(
+        "{} in {} #{} [{}] [{}]",
+        el.name,
+        ancestors.join("/"),
+        ctx.index(),
+        names(ctx.previous_siblings()),
+        names(ctx.next_siblings())
+      ));
+      VisitAction::Keep
+    })
+  }
+
+  fn names(children: &[XMLAstChild]) -> String {
+    let names: Vec<_> = children
+      .iter()
+      .filter_map(|child| match child {
+        XMLAstChild::Element(el) => Some(el.name),
+        _ => None,
+      })
+      .collect();
+    names.join(",")
+  }
+
+  fn element<'a>(arena: &'a Bump, name: &'a str) -> XMLAstChild<'a> {
+    XMLAstChild::Element(XMLAstElement {
+      name,
+      attributes: BumpVec::new_in(arena),
+      children: BumpVec::new_in(arena),
+    })
+  }
+
+  fn optimize<'a>(input: &'a str, arena: &'a Bump, plugins: Vec<NamedPlugin<'a>>) -> String {
+    let mut root = parse_svg(input, arena).unwrap();
+    SvgOptimizer::new(plugins).optimize(&mut root)
+  }
+
+  #[test]
+  fn context_exposes_ancestors_and_siblings() {
+    let arena = Bump::new();
+    let log = Rc::default();
+    let parent = enter(|el, ctx| {
+      if el.name == "a" {
+        let parent = ctx.parent().unwrap();
+        assert_eq!((parent.name, parent.get_attribute("id")), ("g", Some("g")));
+        assert!(parent.has_attribute("id") && !parent.has_attribute("fill"));
+        assert_eq!(parent.parent().unwrap().name, "svg");
+        assert!(parent.parent().unwrap().parent().is_none());
+        assert_eq!(ctx.depth(), 2);
+        assert!(ctx.previous_sibling().is_none());
+        assert!(matches!(ctx.next_sibling(), Some(XMLAstChild::Element(el)) if el.name == "b"));
+      }
+      VisitAction::Keep
+    });
+    let input = r#"<svg><g id="g"><a/><b/><c/></g></svg>"#;
+    optimize(input, &arena, vec![parent, recorder(&log)]);
+    assert_eq!(
+      *log.borrow(),
+      [
+        "svg in  #0 [] []",
+        "g in svg #0 [] []",
+        "a in g/svg #0 [] [b,c]",
+        "b in g/svg #1 [a] [c]",
+        "c in g/svg #2 [a,b] []",
+      ]
+    );
+  }
+
+  #[test]
+  fn index_skips_removed_siblings_and_counts_inserted_ones() {
+    let arena = Bump::new();
+    let log = Rc::default();
+    let remove_b = enter(|el, _| match el.name {
+      "b" => VisitAction::Remove,
+      _ => VisitAction::Keep,
+    });
+    let insert_before_c = enter(|el, _| match el.name {
+      "c" => VisitAction::InsertBefore(vec![element(&arena, "x")]),
+      _ => VisitAction::Keep,
+    });
+    let output = optimize(
+      "<svg><a/><b/><c/><d/></svg>",
+      &arena,
+      vec![remove_b, insert_before_c, recorder(&log)],
+    );
+    assert_eq!(output, "<svg><a/><x/><c/><d/></svg>");
+    // 被删除的 b 不再被后面的插件访问，插入到 c 之前的 x 不会被访问
+    assert_eq!(
+      log.borrow()[1..],
+      [
+        "a in svg #0 [] [b,c,d]",
+        "c in svg #1 [a] [d]",
+        "d in svg #3 [a,x,c] []",
+      ]
+    );
+  }
+
+  /// 在名为 `name` 的元素上返回 `action` 创建的动作
+  fn on<'a>(name: &'a str, action: impl Fn() -> VisitAction<'a> + 'a) -> NamedPlugin<'a> {
+    enter(move |el, _| {
+      if el.name == name {
+        action()
+      } else {
+        VisitAction::Keep
+      }
+    })
+  }
+
+  #[test]
+  fn replacement_is_not_revisited_by_the_plugin_that_produced_it() {
+    let arena = Bump::new();
+    let log = Rc::default();
+    let replaced = Rc::new(RefCell::new(0));
+    let count = replaced.clone();
+    // 没有跳过时，每次都把 a 换成新的 a 会让遍历停不下来
+    let replace_a = enter(|el, _| match el.name {
+      "a" => {
+        *count.borrow_mut() += 1;
+        VisitAction::Replace(element(&arena, "a"))
+      }
+      _ => VisitAction::Keep,
+    });
+    let replace_b = on("b", || VisitAction::Replace(element(&arena, "a")));
+    let output = optimize(
+      "<svg><a/><b/></svg>",
+      &arena,
+      vec![replace_a, replace_b, recorder(&log)],
+    );
+    assert_eq!(output, "<svg><a/><a/></svg>");
+    // b 换成的 a 仍会交给前面的插件
+    assert_eq!(*replaced.borrow(), 2);
+    assert_eq!(
+      log.borrow()[1..],
+      ["a in svg #0 [] [b]", "a in svg #1 [a] []"]
+    );
+  }
+
+  #[test]
+  fn insertions_from_several_plugins_keep_their_order() {
+    let arena = Bump::new();
+    let log = Rc::default();
+    let output = optimize(
+      "<svg><a/><b/></svg>",
+      &arena,
+      vec![
+        on("a", || {
+          VisitAction::InsertBefore(vec![element(&arena, "p")])
+        }),
+        on("a", || {
+          VisitAction::InsertBefore(vec![element(&arena, "q")])
+        }),
+        on("a", || VisitAction::InsertAfter(vec![element(&arena, "r")])),
+        on("a", || VisitAction::InsertAfter(vec![element(&arena, "s")])),
+        recorder(&log),
+      ],
+    );
+    assert_eq!(output, "<svg><p/><q/><a/><r/><s/><b/></svg>");
+    assert_eq!(
+      log.borrow()[1..],
+      [
+        "a in svg #0 [] [b]",
+        "r in svg #3 [p,q,a] [s,b]",
+        "s in svg #4 [p,q,a,r] [b]",
+        "b in svg #5 [p,q,a,r,s] []",
+      ]
+    );
+  }
+
+  #[test]
+  fn insertions_survive_removing_the_node() {
+    let arena = Bump::new();
+    let log = Rc::default();
+    let output = optimize(
+      "<svg><a/><b/></svg>",
+      &arena,
+      vec![
+        on("a", || {
+          VisitAction::InsertBefore(vec![element(&arena, "y")])
+        }),
+        on("a", || VisitAction::InsertAfter(vec![element(&arena, "x")])),
+        on("a", || VisitAction::Remove),
+        recorder(&log),
+      ],
+    );
+    assert_eq!(output, "<svg><y/><x/><b/></svg>");
+    assert_eq!(
+      log.borrow()[1..],
+      ["x in svg #1 [y] [b]", "b in svg #2 [y,x] []"]
+    );
+  }
+
+  #[test]
+  fn insertions_go_around_the_children_of_a_replaced_element() {
+    let arena = Bump::new();
+    let log = Rc::default();
+    let output = optimize(
+      "<svg><g><c/><d/></g><b/></svg>",
+      &arena,
+      vec![
+        on("g", || {
+          VisitAction::InsertBefore(vec![element(&arena, "y")])
+        }),
+        on("g", || VisitAction::InsertAfter(vec![element(&arena, "x")])),
+        on("g", || VisitAction::ReplaceWithChildren),
+        recorder(&log),
+      ],
+    );
+    assert_eq!(output, "<svg><y/><c/><d/><x/><b/></svg>");
+    assert_eq!(
+      log.borrow()[1..],
+      [
+        "c in svg #1 [y] [d,x,b]",
+        "d in svg #2 [y,c] [x,b]",
+        "x in svg #3 [y,c,d] [b]",
+        "b in svg #4 [y,c,d,x] []",
+      ]
+    );
+  }
+}
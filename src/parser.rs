@@ -63,6 +63,29 @@ pub struct XMLAstElement<'arena> {
   pub children: BumpVec<'arena, XMLAstChild<'arena>>,
 }
 
+impl<'arena> XMLAstElement<'arena> {
+  /// 获取属性值
+  pub fn get_attribute(&self, name: &str) -> Option<&'arena str> {
+    self.attributes.iter().find(|(key, _)| *key == name).map(|(_, value)| *value)
+  }
+
+  pub fn has_attribute(&self, name: &str) -> bool {
+    self.attributes.iter().any(|(key, _)| *key == name)
+  }
+
+  /// 设置属性值，已存在时原地替换以保持属性顺序
+  pub fn set_attribute(&mut self, name: &'arena str, value: &'arena str) {
+    match self.attributes.iter_mut().find(|(key, _)| *key == name) {
+      Some(attr) => attr.1 = value,
+      None => self.attributes.push((name, value)),
+    }
+  }
+
+  pub fn remove_attribute(&mut self, name: &str) {
+    self.attributes.retain(|(key, _)| *key != name);
+  }
+}
+
 /// XMLAstChild: 所有非根节点的合集
 #[derive(Debug, Clone)]
 pub enum XMLAstChild<'arena> {
@@ -0,0 +1,934 @@
+use std::f64::consts::PI;
+
+use crate::transform::Matrix;
+
+/// 路径数据中的一条命令，参数保持原样（可能是相对坐标）
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathItem {
+  pub command: char,
+  pub args: Vec<f64>,
+}
+
+/// 转为绝对坐标后的路径片段，弧线已转换为三次贝塞尔曲线
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment {
+  MoveTo(Point),
+  LineTo(Point),
+  QuadTo(Point, Point),
+  CubicTo(Point, Point, Point),
+  Close,
+}
+
+pub type Point = (f64, f64);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+  pub min_x: f64,
+  pub min_y: f64,
+  pub max_x: f64,
+  pub max_y: f64,
+}
+
+impl BoundingBox {
+  fn from_point((x, y): Point) -> Self {
+    BoundingBox {
+      min_x: x,
+      min_y: y,
+      max_x: x,
+      max_y: y,
+    }
+  }
+
+  fn add(&mut self, (x, y): Point) {
+    self.min_x = self.min_x.min(x);
+    self.min_y = self.min_y.min(y);
+    self.max_x = self.max_x.max(x);
+    self.max_y = self.max_y.max(y);
+  }
+
+  pub fn intersects(&self, other: &BoundingBox) -> bool {
+    self.min_x <= other.max_x
+      && other.min_x <= self.max_x
+      && self.min_y <= other.max_y
+      && other.min_y <= self.max_y
+  }
+}
+
+fn args_count(command: char) -> Option<usize> {
+  match command.to_ascii_lowercase() {
+    'm' | 'l' | 't' => Some(2),
+    'h' | 'v' => Some(1),
+    'c' => Some(6),
+    's' | 'q' => Some(4),
+    'a' => Some(7),
+    'z' => Some(0),
+    _ => None,
+  }
+}
+
+/// 解析 `d` 属性。
+///
+/// 与浏览器的行为一致，遇到错误时保留已经解析成功的部分。
+pub fn parse_path_data(d: &str) -> Vec<PathItem> {
+  parse_path_prefix(d).0
+}
+
+/// 解析 `d` 属性，有任何部分无法解析时返回 `None`，用于需要改写整个 `d` 的场合
+pub fn parse_complete_path_data(d: &str) -> Option<Vec<PathItem>> {
+  let (items, complete) = parse_path_prefix(d);
+  complete.then_some(items)
+}
+
+/// 返回能解析的部分，以及是否解析到了末尾
+fn parse_path_prefix(d: &str) -> (Vec<PathItem>, bool) {
+  let bytes = d.as_bytes();
+  let mut items = Vec::new();
+  let mut pos = 0;
+  let mut command: Option<char> = None;
+  let mut args = Vec::new();
+
+  loop {
+    pos = skip_separators(bytes, pos);
+    if pos >= bytes.len() {
+      break;
+    }
+    let c = bytes[pos] as char;
+    if args_count(c).is_some() {
+      // 上一条命令的参数不完整
+      if command.is_some() && !args.is_empty() {
+        break;
+      }
+      if command.is_none() && !matches!(c, 'M' | 'm') {
+        break;
+      }
+      pos += 1;
+      if matches!(c, 'Z' | 'z') {
+        items.push(PathItem {
+          command: c,
+          args: vec![],
+        });
+        command = Some(c);
+        continue;
+      }
+      command = Some(c);
+      continue;
+    }
+
+    let Some(current) = command else {
+      break;
+    };
+    // z 之后不能直接跟数字
+    if matches!(current, 'Z' | 'z') {
+      break;
+    }
+    let count = args_count(current).unwrap_or(0);
+    // 弧线的两个 flag 可以不带分隔符，例如 `a1 1 0 00 1 1`
+    let is_flag = current.eq_ignore_ascii_case(&'a') && (args.len() == 3 || args.len() == 4);
+    let number = if is_flag {
+      match bytes[pos] {
+        b'0' => Some((0.0, pos + 1)),
+        b'1' => Some((1.0, pos + 1)),
+        _ => None,
+      }
+    } else {
+      read_number(bytes, pos)
+    };
+    let Some((value, next)) = number else {
+      break;
+    };
+    pos = next;
+    args.push(value);
+    if args.len() == count {
+      items.push(PathItem {
+        command: current,
+        args: std::mem::take(&mut args),
+      });
+      // moveto 之后的隐式坐标视为 lineto
+      command = match current {
+        'M' => Some('L'),
+        'm' => Some('l'),
+        other => Some(other),
+      };
+    }
+  }
+  // 出错时都停在无法解析的字符上
+  (items, pos >= bytes.len() && args.is_empty())
+}
+
+fn skip_separators(bytes: &[u8], mut pos: usize) -> usize {
+  while pos < bytes.len() && (bytes[pos].is_ascii_whitespace() || bytes[pos] == b',') {
+    pos += 1;
+  }
+  pos
+}
+
+/// 从 `pos` 开始读取一个数字，返回数值和结束位置
+pub(crate) fn read_number(bytes: &[u8], start: usize) -> Option<(f64, usize)> {
+  let mut pos = start;
+  if pos < bytes.len() && (bytes[pos] == b'+' || bytes[pos] == b'-') {
+    pos += 1;
+  }
+  let mut has_digits = false;
+  while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+    pos += 1;
+    has_digits = true;
+  }
+  if pos < bytes.len() && bytes[pos] == b'.' {
+    pos += 1;
+    while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+      pos += 1;
+      has_digits = true;
+    }
+  }
+  if !has_digits {
+    return None;
+  }
+  if pos < bytes.len() && (bytes[pos] == b'e' || bytes[pos] == b'E') {
+    let mut exp = pos + 1;
+    if exp < bytes.len() && (bytes[exp] == b'+' || bytes[exp] == b'-') {
+      exp += 1;
+    }
+    if exp < bytes.len() && bytes[exp].is_ascii_digit() {
+      while exp < bytes.len() && bytes[exp].is_ascii_digit() {
+        exp += 1;
+      }
+      pos = exp;
+    }
+  }
+  let text = std::str::from_utf8(&bytes[start..pos]).ok()?;
+  text.parse::<f64>().ok().map(|value| (value, pos))
+}
+
+/// 解析由空白或逗号分隔的数字列表，允许 `10-5` 这样的紧凑写法
+pub(crate) fn parse_number_list(value: &str) -> Option<Vec<f64>> {
+  let bytes = value.as_bytes();
+  let mut numbers = Vec::new();
+  let mut pos = skip_separators(bytes, 0);
+  while pos < bytes.len() {
+    let (number, next) = read_number(bytes, pos)?;
+    numbers.push(number);
+    pos = skip_separators(bytes, next);
+  }
+  Some(numbers)
+}
+
+/// 把路径命令转为绝对坐标的片段
+pub fn to_segments(items: &[PathItem]) -> Vec<Segment> {
+  let mut segments = Vec::with_capacity(items.len());
+  let mut current = (0.0, 0.0);
+  let mut start = (0.0, 0.0);
+  // 上一条曲线的控制点，用于 S/T 的反射
+  let mut prev_cubic_control: Option<Point> = None;
+  let mut prev_quad_control: Option<Point> = None;
+
+  for item in items {
+    let relative = item.command.is_ascii_lowercase();
+    let (ox, oy) = if relative { current } else { (0.0, 0.0) };
+    let point = |i: usize| (item.args[i] + ox, item.args[i + 1] + oy);
+    let mut cubic_control = None;
+    let mut quad_control = None;
+
+    match item.command.to_ascii_lowercase() {
+      'm' => {
+        current = point(0);
+        start = current;
+        segments.push(Segment::MoveTo(current));
+      }
+      'l' => {
+        current = point(0);
+        segments.push(Segment::LineTo(current));
+      }
+      'h' => {
+        current = (item.args[0] + ox, current.1);
+        segments.push(Segment::LineTo(current));
+      }
+      'v' => {
+        current = (current.0, item.args[0] + oy);
+        segments.push(Segment::LineTo(current));
+      }
+      'c' => {
+        let (c1, c2, end) = (point(0), point(2), point(4));
+        segments.push(Segment::CubicTo(c1, c2, end));
+        cubic_control = Some(c2);
+        current = end;
+      }
+      's' => {
+        let c1 = reflect(prev_cubic_control, current);
+        let (c2, end) = (point(0), point(2));
+        segments.push(Segment::CubicTo(c1, c2, end));
+        cubic_control = Some(c2);
+        current = end;
+      }
+      'q' => {
+        let (c, end) = (point(0), point(2));
+        segments.push(Segment::QuadTo(c, end));
+        quad_control = Some(c);
+        current = end;
+      }
+      't' => {
+        let c = reflect(prev_quad_control, current);
+        let end = point(0);
+        segments.push(Segment::QuadTo(c, end));
+        quad_control = Some(c);
+        current = end;
+      }
+      'a' => {
+        let end = point(5);
+        arc_to_cubics(
+          current,
+          (item.args[0], item.args[1]),
+          item.args[2],
+          item.args[3] != 0.0,
+          item.args[4] != 0.0,
+          end,
+          &mut segments,
+        );
+        current = end;
+      }
+      'z' => {
+        segments.push(Segment::Close);
+        current = start;
+      }
+      _ => {}
+    }
+    prev_cubic_control = cubic_control;
+    prev_quad_control = quad_control;
+  }
+  segments
+}
+
+fn reflect(control: Option<Point>, current: Point) -> Point {
+  match control {
+    Some((x, y)) => (2.0 * current.0 - x, 2.0 * current.1 - y),
+    None => current,
+  }
+}
+
+/// 按 SVG 规范（F.6.5）把椭圆弧转换为三次贝塞尔曲线，每段不超过 90°
+fn arc_to_cubics(
+  from: Point,
+  (rx, ry): Point,
+  angle: f64,
+  large_arc: bool,
+  sweep: bool,
+  to: Point,
+  segments: &mut Vec<Segment>,
+) {
+  if from == to {
+    return;
+  }
+  let (mut rx, mut ry) = (rx.abs(), ry.abs());
+  if rx == 0.0 || ry == 0.0 {
+    segments.push(Segment::LineTo(to));
+    return;
+  }
+
+  let (sin_phi, cos_phi) = angle.to_radians().sin_cos();
+  let dx = (from.0 - to.0) / 2.0;
+  let dy = (from.1 - to.1) / 2.0;
+  let x1 = cos_phi * dx + sin_phi * dy;
+  let y1 = -sin_phi * dx + cos_phi * dy;
+
+  let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+  if lambda > 1.0 {
+    rx *= lambda.sqrt();
+    ry *= lambda.sqrt();
+  }
+
+  let numerator = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+  let denominator = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+  let sign = if large_arc != sweep { 1.0 } else { -1.0 };
+  let coef = sign * (numerator / denominator).max(0.0).sqrt();
+  let cx1 = coef * rx * y1 / ry;
+  let cy1 = -coef * ry * x1 / rx;
+  let cx = cos_phi * cx1 - sin_phi * cy1 + (from.0 + to.0) / 2.0;
+  let cy = sin_phi * cx1 + cos_phi * cy1 + (from.1 + to.1) / 2.0;
+
+  let vector_angle =
+    |ux: f64, uy: f64, vx: f64, vy: f64| (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+  let ux = (x1 - cx1) / rx;
+  let uy = (y1 - cy1) / ry;
+  let vx = (-x1 - cx1) / rx;
+  let vy = (-y1 - cy1) / ry;
+  let theta = vector_angle(1.0, 0.0, ux, uy);
+  let mut delta = vector_angle(ux, uy, vx, vy) % (2.0 * PI);
+  if !sweep && delta > 0.0 {
+    delta -= 2.0 * PI;
+  } else if sweep && delta < 0.0 {
+    delta += 2.0 * PI;
+  }
+
+  let map = |ux: f64, uy: f64| {
+    (
+      cx + rx * cos_phi * ux - ry * sin_phi * uy,
+      cy + rx * sin_phi * ux + ry * cos_phi * uy,
+    )
+  };
+  let count = (delta.abs() / (PI / 2.0)).ceil().max(1.0) as usize;
+  let step = delta / count as f64;
+  let k = 4.0 / 3.0 * (step / 4.0).tan();
+  for i in 0..count {
+    let start = theta + step * i as f64;
+    let end = start + step;
+    let (sin_start, cos_start) = start.sin_cos();
+    let (sin_end, cos_end) = end.sin_cos();
+    let c1 = map(cos_start - k * sin_start, sin_start + k * cos_start);
+    let c2 = map(cos_end + k * sin_end, sin_end - k * cos_end);
+    // 最后一段直接使用终点，避免浮点误差
+    let end_point = if i == count - 1 {
+      to
+    } else {
+      map(cos_end, sin_end)
+    };
+    segments.push(Segment::CubicTo(c1, c2, end_point));
+  }
+}
+
+/// 对所有片段应用变换矩阵
+pub fn transform_segments(segments: &mut [Segment], matrix: &Matrix) {
+  let apply = |(x, y): Point| matrix.apply(x, y);
+  for segment in segments.iter_mut() {
+    *segment = match *segment {
+      Segment::MoveTo(p) => Segment::MoveTo(apply(p)),
+      Segment::LineTo(p) => Segment::LineTo(apply(p)),
+      Segment::QuadTo(c, p) => Segment::QuadTo(apply(c), apply(p)),
+      Segment::CubicTo(c1, c2, p) => Segment::CubicTo(apply(c1), apply(c2), apply(p)),
+      Segment::Close => Segment::Close,
+    };
+  }
+}
+
+/// 计算精确的包围盒，曲线会计算其极值点
+pub fn bounding_box(segments: &[Segment]) -> Option<BoundingBox> {
+  let mut bbox: Option<BoundingBox> = None;
+  let mut add = |point: Point| match bbox.as_mut() {
+    Some(bbox) => bbox.add(point),
+    None => bbox = Some(BoundingBox::from_point(point)),
+  };
+  let mut current = (0.0, 0.0);
+  let mut start = (0.0, 0.0);
+  for segment in segments {
+    match *segment {
+      Segment::MoveTo(p) => {
+        add(p);
+        current = p;
+        start = p;
+      }
+      Segment::LineTo(p) => {
+        add(p);
+        current = p;
+      }
+      Segment::QuadTo(c, p) => {
+        add(p);
+        for t in [
+          quad_extremum(current.0, c.0, p.0),
+          quad_extremum(current.1, c.1, p.1),
+        ]
+        .into_iter()
+        .flatten()
+        {
+          add(quad_point(current, c, p, t));
+        }
+        current = p;
+      }
+      Segment::CubicTo(c1, c2, p) => {
+        add(p);
+        let xs = cubic_extrema(current.0, c1.0, c2.0, p.0);
+        let ys = cubic_extrema(current.1, c1.1, c2.1, p.1);
+        for t in xs.into_iter().chain(ys).flatten() {
+          add(cubic_point(current, c1, c2, p, t));
+        }
+        current = p;
+      }
+      Segment::Close => current = start,
+    }
+  }
+  bbox
+}
+
+fn quad_extremum(p0: f64, p1: f64, p2: f64) -> Option<f64> {
+  let denominator = p0 - 2.0 * p1 + p2;
+  if denominator == 0.0 {
+    return None;
+  }
+  let t = (p0 - p1) / denominator;
+  (t > 0.0 && t < 1.0).then_some(t)
+}
+
+fn quad_point(p0: Point, p1: Point, p2: Point, t: f64) -> Point {
+  let mt = 1.0 - t;
+  (
+    mt * mt * p0.0 + 2.0 * mt * t * p1.0 + t * t * p2.0,
+    mt * mt * p0.1 + 2.0 * mt * t * p1.1 + t * t * p2.1,
+  )
+}
+
+/// 三次贝塞尔曲线导数为 0 的参数 t，最多两个
+fn cubic_extrema(p0: f64, p1: f64, p2: f64, p3: f64) -> [Option<f64>; 2] {
+  let a = -p0 + 3.0 * p1 - 3.0 * p2 + p3;
+  let b = 2.0 * (p0 - 2.0 * p1 + p2);
+  let c = p1 - p0;
+  let in_range = |t: f64| (t > 0.0 && t < 1.0).then_some(t);
+  if a.abs() < 1e-12 {
+    if b.abs() < 1e-12 {
+      return [None, None];
+    }
+    return [in_range(-c / b), None];
+  }
+  let discriminant = b * b - 4.0 * a * c;
+  if discriminant < 0.0 {
+    return [None, None];
+  }
+  let sqrt = discriminant.sqrt();
+  [
+    in_range((-b + sqrt) / (2.0 * a)),
+    in_range((-b - sqrt) / (2.0 * a)),
+  ]
+}
+
+fn cubic_point(p0: Point, p1: Point, p2: Point, p3: Point, t: f64) -> Point {
+  let mt = 1.0 - t;
+  let (a, b, c, d) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+  (
+    a * p0.0 + b * p1.0 + c * p2.0 + d * p3.0,
+    a * p0.1 + b * p1.1 + c * p2.1 + d * p3.1,
+  )
+}
+
+/// 把变换应用到路径坐标上，保留命令的相对/绝对形式。
+///
+/// 变换后不再水平或竖直的 `H`/`V` 会改写为 `L`。
+pub fn transform_path_data(items: &[PathItem], matrix: &Matrix) -> Vec<PathItem> {
+  let mut result = Vec::with_capacity(items.len());
+  let mut current = (0.0, 0.0);
+  let mut start = (0.0, 0.0);
+  // 旋转 90° 等情况下 sin/cos 会有极小的误差
+  let is_zero = |value: f64| value.abs() < 1e-12;
+  let axis_aligned = is_zero(matrix.b) && is_zero(matrix.c);
+  let axis_swapped = is_zero(matrix.a) && is_zero(matrix.d);
+
+  for (index, item) in items.iter().enumerate() {
+    // 第一个 `m` 总是按绝对坐标处理
+    let relative = item.command.is_ascii_lowercase() && index > 0;
+    let (ox, oy) = if relative { current } else { (0.0, 0.0) };
+    let map = |x: f64, y: f64| {
+      if relative {
+        matrix.apply_linear(x, y)
+      } else {
+        matrix.apply(x, y)
+      }
+    };
+    let with_case = |command: char| {
+      if relative {
+        command
+      } else {
+        command.to_ascii_uppercase()
+      }
+    };
+
+    let lower = item.command.to_ascii_lowercase();
+    let mut command = with_case(lower);
+    let mut args = item.args.clone();
+    match lower {
+      'm' | 'l' | 't' | 'c' | 's' | 'q' => {
+        for pair in args.chunks_exact_mut(2) {
+          let (x, y) = map(pair[0], pair[1]);
+          pair[0] = x;
+          pair[1] = y;
+        }
+        let end = &item.args[item.args.len() - 2..];
+        current = (end[0] + ox, end[1] + oy);
+        if lower == 'm' {
+          start = current;
+        }
+      }
+      'h' | 'v' => {
+        let horizontal = lower == 'h';
+        let (dx, dy) = if horizontal {
+          (item.args[0], 0.0)
+        } else {
+          (0.0, item.args[0])
+        };
+        // 原坐标系中的终点，相对命令取位移
+        let (x, y) = if relative {
+          (dx, dy)
+        } else if horizontal {
+          (item.args[0], current.1)
+        } else {
+          (current.0, item.args[0])
+        };
+        let (nx, ny) = map(x, y);
+        if axis_aligned {
+          args = vec![if horizontal { nx } else { ny }];
+        } else if axis_swapped {
+          command = with_case(if horizontal { 'v' } else { 'h' });
+          args = vec![if horizontal { ny } else { nx }];
+        } else {
+          command = with_case('l');
+          args = vec![nx, ny];
+        }
+        current = (x + ox, y + oy);
+      }
+      'a' => {
+        let end = (item.args[5] + ox, item.args[6] + oy);
+        let (rx, ry, angle, sweep) = transform_arc(
+          (end.0 - current.0, end.1 - current.1),
+          (item.args[0], item.args[1]),
+          item.args[2],
+          item.args[4],
+          matrix,
+        );
+        let (x, y) = map(item.args[5], item.args[6]);
+        args = vec![rx, ry, angle, item.args[3], sweep, x, y];
+        current = end;
+      }
+      'z' => current = start,
+      _ => {}
+    }
+    result.push(PathItem { command, args });
+  }
+  result
+}
+
+/// 变换椭圆弧的半径、旋转角和 sweep flag，`vector` 是原坐标系中起点到终点的向量
+fn transform_arc(
+  (x, y): Point,
+  (rx, ry): Point,
+  angle: f64,
+  sweep: f64,
+  matrix: &Matrix,
+) -> (f64, f64, f64, f64) {
+  let (mut rx, mut ry) = (rx.abs(), ry.abs());
+  let (sin, cos) = angle.to_radians().sin_cos();
+  // 半径不足以连接两个端点时按规范放大
+  if rx > 0.0 && ry > 0.0 {
+    let h =
+      (x * cos + y * sin).powi(2) / (4.0 * rx * rx) + (y * cos - x * sin).powi(2) / (4.0 * ry * ry);
+    if h > 1.0 {
+      rx *= h.sqrt();
+      ry *= h.sqrt();
+    }
+  }
+
+  // 单位圆到原椭圆的矩阵，再叠加变换后分解出新的椭圆
+  let ellipse = Matrix {
+    a: rx * cos,
+    b: rx * sin,
+    c: -ry * sin,
+    d: ry * cos,
+    e: 0.0,
+    f: 0.0,
+  };
+  let m = matrix.multiply(&ellipse);
+  let last_col = m.c * m.c + m.d * m.d;
+  let square_sum = m.a * m.a + m.b * m.b + last_col;
+  let root = (m.a - m.d).hypot(m.b + m.c) * (m.a + m.d).hypot(m.b - m.c);
+  let (new_rx, new_ry, new_angle) = if root == 0.0 {
+    let radius = (square_sum / 2.0).sqrt();
+    (radius, radius, 0.0)
+  } else {
+    let major_sqr = (square_sum + root) / 2.0;
+    let minor_sqr = (square_sum - root) / 2.0;
+    let major = (major_sqr - last_col).abs() > 1e-6;
+    let sub = if major { major_sqr } else { minor_sqr } - last_col;
+    let rows_sum = m.a * m.c + m.b * m.d;
+    let term1 = m.a * sub + m.c * rows_sum;
+    let term2 = m.b * sub + m.d * rows_sum;
+    let sign = if major { term2 < 0.0 } else { term1 > 0.0 };
+    let cos_angle = if major { term1 } else { term2 } / term1.hypot(term2);
+    let angle = if sign { -1.0 } else { 1.0 } * cos_angle.acos().to_degrees();
+    (major_sqr.sqrt(), minor_sqr.sqrt(), angle)
+  };
+  // 镜像会改变弧线的方向
+  let sweep = if matrix.determinant() < 0.0 {
+    1.0 - sweep
+  } else {
+    sweep
+  };
+  (new_rx, new_ry, new_angle, sweep)
+}
+
+/// 按指定精度输出数字，省略多余的 0
+pub(crate) fn format_number(value: f64, precision: usize) -> String {
+  let formatted = format!("{:.*}", precision, value);
+  let trimmed = if formatted.contains('.') {
+    formatted.trim_end_matches('0').trim_end_matches('.')
+  } else {
+    &formatted
+  };
+  match trimmed {
+    "-0" | "" => "0".to_string(),
+    _ => match trimmed.strip_prefix("-0.") {
+      Some(fraction) => format!("-.{}", fraction),
+      None => match trimmed.strip_prefix("0.") {
+        Some(fraction) => format!(".{}", fraction),
+        None => trimmed.to_string(),
+      },
+    },
+  }
+}
+
+/// 输出紧凑的路径数据，连续相同的命令省略命令字母
+pub fn stringify_path_data(items: &[PathItem], precision: usize) -> String {
+  let mut output = String::new();
+  let mut previous: Option<char> = None;
+  let mut last_number: Option<String> = None;
+  for item in items {
+    // moveto 之后的同名命令会被解析为 lineto，不能省略
+    let implicit =
+      previous == Some(item.command) && !item.args.is_empty() && !matches!(item.command, 'M' | 'm');
+    if !implicit {
+      output.push(item.command);
+      last_number = None;
+    }
+    for value in item.args.iter() {
+      let number = format_number(*value, precision);
+      if let Some(last) = &last_number {
+        // 负号和紧跟在小数后的小数点都可以作为分隔符
+        let joinable = number.starts_with('-') || (number.starts_with('.') && last.contains('.'));
+        if !joinable {
+          output.push(' ');
+        }
+      }
+      output.push_str(&number);
+      last_number = Some(number);
+    }
+    previous = Some(item.command);
+  }
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn item(command: char, args: &[f64]) -> PathItem {
+    PathItem {
+      command,
+      args: args.to_vec(),
+    }
+  }
+
+  fn assert_point_eq((x, y): Point, expected: Point) {
+    assert!(
+      (x - expected.0).abs() < 1e-9 && (y - expected.1).abs() < 1e-9,
+      "{:?} != {:?}",
+      (x, y),
+      expected
+    );
+  }
+
+  #[test]
+  fn parse_repeats_implicit_commands() {
+    assert_eq!(
+      parse_path_data("M1 2 3 4m5 6 7 8h1 2"),
+      [
+        item('M', &[1.0, 2.0]),
+        item('L', &[3.0, 4.0]),
+        item('m', &[5.0, 6.0]),
+        item('l', &[7.0, 8.0]),
+        item('h', &[1.0]),
+        item('h', &[2.0]),
+      ]
+    );
+  }
+
+  #[test]
+  fn parse_splits_packed_numbers_and_arc_flags() {
+    assert_eq!(
+      parse_path_data("M10-20.5.5.5L1e2,-3E-1"),
+      [
+        item('M', &[10.0, -20.5]),
+        item('L', &[0.5, 0.5]),
+        item('L', &[100.0, -0.3]),
+      ]
+    );
+    assert_eq!(
+      parse_path_data("M0 0a10 10 30 1120 30"),
+      [
+        item('M', &[0.0, 0.0]),
+        item('a', &[10.0, 10.0, 30.0, 1.0, 1.0, 20.0, 30.0]),
+      ]
+    );
+    // flag 只能是 0 或 1
+    assert_eq!(
+      parse_path_data("M0 0a10 10 30 2 0 20 30"),
+      [item('M', &[0.0, 0.0])]
+    );
+  }
+
+  #[test]
+  fn parse_keeps_the_valid_prefix_and_reports_whether_it_is_complete() {
+    let cases: [(&str, &[PathItem]); 5] = [
+      ("garbage", &[]),
+      ("L1 1", &[]),
+      (
+        "M0 0L10 10 garbage",
+        &[item('M', &[0.0, 0.0]), item('L', &[10.0, 10.0])],
+      ),
+      ("M0 0L10", &[item('M', &[0.0, 0.0])]),
+      ("M0 0z1", &[item('M', &[0.0, 0.0]), item('z', &[])]),
+    ];
+    for (d, prefix) in cases {
+      assert_eq!(parse_path_data(d), prefix, "{}", d);
+      assert_eq!(parse_complete_path_data(d), None, "{}", d);
+    }
+    assert_eq!(parse_complete_path_data(" "), Some(vec![]));
+    assert_eq!(
+      parse_complete_path_data("M0 0z"),
+      Some(vec![item('M', &[0.0, 0.0]), item('z', &[])])
+    );
+  }
+
+  #[test]
+  fn segments_are_absolute_and_reflect_controls() {
+    let segments = to_segments(&parse_path_data(
+      "M10 10l10 0zl5 5M0 0C0 10 10 10 10 0S20-10 20 0",
+    ));
+    assert_eq!(
+      segments,
+      [
+        Segment::MoveTo((10.0, 10.0)),
+        Segment::LineTo((20.0, 10.0)),
+        Segment::Close,
+        Segment::LineTo((15.0, 15.0)),
+        Segment::MoveTo((0.0, 0.0)),
+        Segment::CubicTo((0.0, 10.0), (10.0, 10.0), (10.0, 0.0)),
+        Segment::CubicTo((10.0, -10.0), (20.0, -10.0), (20.0, 0.0)),
+      ]
+    );
+  }
+
+  #[test]
+  fn arcs_become_quarter_cubics() {
+    let arc = |from, radii, sweep, to| {
+      let mut segments = Vec::new();
+      arc_to_cubics(from, radii, 0.0, false, sweep, to, &mut segments);
+      segments
+    };
+    let half = arc((0.0, 0.0), (10.0, 10.0), true, (20.0, 0.0));
+    let [Segment::CubicTo(_, _, middle), Segment::CubicTo(_, _, end)] = half[..] else {
+      panic!("expected two cubics, got {:?}", half);
+    };
+    assert_point_eq(middle, (10.0, -10.0));
+    assert_eq!(end, (20.0, 0.0));
+    // 半径太小时按比例放大，sweep 决定方向
+    let scaled = arc((0.0, 0.0), (1.0, 1.0), false, (20.0, 0.0));
+    let Segment::CubicTo(_, _, middle) = scaled[0] else {
+      panic!("expected a cubic, got {:?}", scaled);
+    };
+    assert_point_eq(middle, (10.0, 10.0));
+    assert_eq!(
+      arc((0.0, 0.0), (0.0, 10.0), true, (20.0, 0.0)),
+      [Segment::LineTo((20.0, 0.0))]
+    );
+    assert!(arc((5.0, 5.0), (10.0, 10.0), true, (5.0, 5.0)).is_empty());
+  }
+
+  #[test]
+  fn transform_keeps_commands_where_possible() {
+    let transform = |d: &str, transform: &str| {
+      let matrix = crate::transform::parse_transform(transform).unwrap();
+      stringify_path_data(&transform_path_data(&parse_path_data(d), &matrix), 3)
+    };
+    assert_eq!(
+      transform("M0 0h10v10z", "translate(10 20)"),
+      "M10 20h10v10z"
+    );
+    // 第一个 m 按绝对坐标处理，输出为 M
+    assert_eq!(transform("m1 1l1 0", "translate(10)"), "M11 1l1 0");
+    assert_eq!(transform("M0 0H10", "rotate(90)"), "M0 0V10");
+    assert_eq!(transform("M0 0V10", "skewX(45)"), "M0 0L10 10");
+    assert_eq!(
+      transform("M0 0A5 5 0 0 1 10 0", "scale(2)"),
+      "M0 0A10 10 0 0 1 20 0"
+    );
+    assert_eq!(
+      transform("M0 0A5 5 0 0 1 10 0", "scale(2 1)"),
+      "M0 0A10 5 0 0 1 20 0"
+    );
+  }
+
+  #[test]
+  fn transform_arc_rotates_radii_and_flips_sweep() {
+    let (rx, ry, angle, sweep) =
+      transform_arc((20.0, 0.0), (10.0, 5.0), 0.0, 1.0, &Matrix::rotate(90.0));
+    assert_point_eq((rx, ry), (10.0, 5.0));
+    assert!((angle.abs() - 90.0).abs() < 1e-9, "{}", angle);
+    assert_eq!(sweep, 1.0);
+    // 镜像改变方向
+    let mirrored = transform_arc((10.0, 0.0), (5.0, 5.0), 0.0, 1.0, &Matrix::scale(-1.0, 1.0));
+    assert_eq!(mirrored, (5.0, 5.0, 0.0, 0.0));
+    // 半径不足以连接端点时先放大
+    let (rx, ry, _, _) = transform_arc((20.0, 0.0), (1.0, 1.0), 0.0, 0.0, &Matrix::IDENTITY);
+    assert_point_eq((rx, ry), (10.0, 10.0));
+  }
+
+  fn bbox(d: &str) -> BoundingBox {
+    bounding_box(&to_segments(&parse_path_data(d))).unwrap()
+  }
+
+  fn assert_bbox_eq(d: &str, expected: [f64; 4]) {
+    let BoundingBox {
+      min_x,
+      min_y,
+      max_x,
+      max_y,
+    } = bbox(d);
+    let actual = [min_x, min_y, max_x, max_y];
+    let close = actual.iter().zip(expected).all(|(a, e)| (a - e).abs() < 1e-9);
+    assert!(close, "{}: {:?} != {:?}", d, actual, expected);
+  }
+
+  #[test]
+  fn bounding_box_includes_curve_extrema() {
+    assert_bbox_eq("M10 10h10v10z", [10.0, 10.0, 20.0, 20.0]);
+    // 控制点不在包围盒中，只有曲线的极值点在
+    assert_bbox_eq("M0 0Q50 100 100 0", [0.0, 0.0, 100.0, 50.0]);
+    assert_bbox_eq("M0 0C0 100 100 100 100 0", [0.0, 0.0, 100.0, 75.0]);
+    assert_bbox_eq("M0 0C-50 0-50 100 0 100", [-37.5, 0.0, 0.0, 100.0]);
+    // 弧线转为曲线后同样计算极值
+    assert_bbox_eq("M0 0A10 10 0 0 1 20 0", [0.0, -10.0, 20.0, 0.0]);
+    // 单调的曲线没有内部极值
+    assert_bbox_eq("M0 0C10 10 20 20 30 30", [0.0, 0.0, 30.0, 30.0]);
+    assert_eq!(bounding_box(&[]), None);
+  }
+
+  #[test]
+  fn bounding_boxes_touching_at_an_edge_intersect() {
+    let view_box = bbox("M0 0H100V100H0z");
+    assert!(bbox("M100 50h10").intersects(&view_box));
+    assert!(bbox("M-10-10L50 50").intersects(&view_box));
+    assert!(!bbox("M100.5 0v100").intersects(&view_box));
+    assert!(!bbox("M0 0Q50-100 100 0").intersects(&bbox("M0 1h100")));
+  }
+
+  #[test]
+  fn numbers_are_printed_without_redundant_characters() {
+    let cases = [
+      (0.5, 3, ".5"),
+      (-0.5, 3, "-.5"),
+      (1.0, 3, "1"),
+      (-0.0001, 3, "0"),
+      (1.23456, 3, "1.235"),
+      (100.0, 0, "100"),
+      (-12.5, 1, "-12.5"),
+    ];
+    for (value, precision, expected) in cases {
+      assert_eq!(format_number(value, precision), expected);
+    }
+  }
+
+  #[test]
+  fn stringify_joins_numbers_and_omits_repeated_commands() {
+    let items = [
+      item('M', &[0.0, 0.0]),
+      item('L', &[0.5, 0.5]),
+      item('L', &[-1.0, 1.0]),
+      item('M', &[1.0, 1.0]),
+      item('M', &[2.0, 2.0]),
+      item('z', &[]),
+    ];
+    assert_eq!(stringify_path_data(&items, 3), "M0 0L.5.5-1 1M1 1M2 2z");
+  }
+}
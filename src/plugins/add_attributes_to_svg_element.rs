@@ -0,0 +1,60 @@
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+
+/// Add attributes to the outer `<svg>` element.
+///
+/// ```svg
+/// <svg viewBox="0 0 24 24"/>
+///              ⬇
+/// <svg viewBox="0 0 24 24" aria-hidden="true" focusable="false"/>
+/// ```
+///
+/// 已经存在的属性不会被覆盖。
+pub struct AddAttributesToSVGElementPlugin<'a> {
+  attributes: Vec<(&'a str, &'a str)>,
+}
+
+pub struct AddAttributesToSVGElementPluginConfig {
+  /// 属性名和属性值，只有名字的属性（svgo 中的字符串形式）值为 `None`
+  pub attributes: Vec<(String, Option<String>)>,
+}
+
+impl<'a> AddAttributesToSVGElementPlugin<'a> {
+  pub fn new(config: AddAttributesToSVGElementPluginConfig, arena: &'a Bump) -> Self {
+    AddAttributesToSVGElementPlugin {
+      attributes: config
+        .attributes
+        .iter()
+        .map(|(name, value)| {
+          let name: &str = arena.alloc_str(name);
+          let value: &str = arena.alloc_str(value.as_deref().unwrap_or(""));
+          (name, value)
+        })
+        .collect(),
+    }
+  }
+}
+
+impl<'a> Plugin<'a> for AddAttributesToSVGElementPlugin<'a> {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    if el.name != "svg" || ctx.parent().is_some() {
+      return VisitAction::Keep;
+    }
+    for (name, value) in &self.attributes {
+      if !el.has_attribute(name) {
+        el.attributes.push((name, value));
+      }
+    }
+    VisitAction::Keep
+  }
+}
@@ -0,0 +1,62 @@
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+
+/// Add classnames to the outer `<svg>` element.
+///
+/// ```svg
+/// <svg class="icon"/>
+///              ⬇
+/// <svg class="icon icon-star"/>
+/// ```
+///
+/// 与已有的 `class` 合并，重复的类名只保留一个。
+pub struct AddClassesToSVGElementPlugin<'a> {
+  class_names: Vec<String>,
+  arena: &'a Bump,
+}
+
+pub struct AddClassesToSVGElementPluginConfig {
+  pub class_names: Vec<String>,
+}
+
+impl<'a> AddClassesToSVGElementPlugin<'a> {
+  pub fn new(config: AddClassesToSVGElementPluginConfig, arena: &'a Bump) -> Self {
+    AddClassesToSVGElementPlugin {
+      class_names: config.class_names,
+      arena,
+    }
+  }
+}
+
+impl<'a> Plugin<'a> for AddClassesToSVGElementPlugin<'a> {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    if el.name != "svg" || ctx.parent().is_some() {
+      return VisitAction::Keep;
+    }
+
+    let mut class_list: Vec<&str> = el
+      .get_attribute("class")
+      .map(|class| class.split_whitespace().collect())
+      .unwrap_or_default();
+    for class_name in &self.class_names {
+      for class_name in class_name.split_whitespace() {
+        if !class_list.contains(&class_name) {
+          class_list.push(class_name);
+        }
+      }
+    }
+    let class = self.arena.alloc_str(&class_list.join(" "));
+    el.set_attribute("class", class);
+    VisitAction::Keep
+  }
+}
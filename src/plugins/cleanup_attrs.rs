@@ -0,0 +1,82 @@
+use bumpalo::Bump;
+use regex::Regex;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+
+/// Cleanups attributes from newlines, trailing and repeating spaces.
+///
+/// ```svg
+/// <g attr="  a
+///   b   c "/>
+///              ⬇
+/// <g attr="a b c"/>
+/// ```
+pub struct CleanupAttrsPlugin<'a> {
+  newlines: bool,
+  trim: bool,
+  spaces: bool,
+  newlines_need_space: Regex,
+  newline: Regex,
+  multiple_spaces: Regex,
+  arena: &'a Bump,
+}
+
+pub struct CleanupAttrsPluginConfig {
+  /// 删除换行，两侧都不是空白时替换为空格，默认 `true`
+  pub newlines: Option<bool>,
+  /// 去掉首尾空白，默认 `true`
+  pub trim: Option<bool>,
+  /// 把连续的空白合并为一个空格，默认 `true`
+  pub spaces: Option<bool>,
+}
+
+impl<'a> CleanupAttrsPlugin<'a> {
+  pub fn new(config: CleanupAttrsPluginConfig, arena: &'a Bump) -> Self {
+    CleanupAttrsPlugin {
+      newlines: config.newlines.unwrap_or(true),
+      trim: config.trim.unwrap_or(true),
+      spaces: config.spaces.unwrap_or(true),
+      newlines_need_space: Regex::new(r"(\S)\r?\n(\S)").unwrap(),
+      newline: Regex::new(r"\r?\n").unwrap(),
+      multiple_spaces: Regex::new(r"\s{2,}").unwrap(),
+      arena,
+    }
+  }
+
+  fn cleanup(&self, value: &str) -> String {
+    let mut value = value.to_string();
+    if self.newlines {
+      value = self.newlines_need_space.replace_all(&value, "$1 $2").into_owned();
+      value = self.newline.replace_all(&value, "").into_owned();
+    }
+    if self.trim {
+      value = value.trim().to_string();
+    }
+    if self.spaces {
+      value = self.multiple_spaces.replace_all(&value, " ").into_owned();
+    }
+    value
+  }
+}
+
+impl<'a> Plugin<'a> for CleanupAttrsPlugin<'a> {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    for index in 0..el.attributes.len() {
+      let value = el.attributes[index].1;
+      let cleaned = self.cleanup(value);
+      if cleaned != value {
+        el.attributes[index].1 = self.arena.alloc_str(&cleaned);
+      }
+    }
+    VisitAction::Keep
+  }
+}
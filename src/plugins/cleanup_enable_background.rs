@@ -0,0 +1,111 @@
+use bumpalo::Bump;
+use regex::Regex;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::{XMLAstChild, XMLAstElement, XMLAstRoot};
+
+/// Remove or cleanup `enable-background` attribute when possible.
+///
+/// ```svg
+/// <svg width="100" height="50" enable-background="new 0 0 100 50">
+///              ⬇
+/// <svg width="100" height="50">
+/// ```
+///
+/// 只有 `in`/`in2` 为 `BackgroundImage` 或 `BackgroundAlpha` 的滤镜才会用到它，
+/// 没有这样的滤镜时直接删除（包括 `style` 中的声明）；否则仅当它与
+/// `<svg>`、`<mask>`、`<pattern>` 的尺寸一致时删除或简化为 `new`。
+pub struct CleanupEnableBackgroundPlugin<'a> {
+  uses_background: bool,
+  enable_background: Regex,
+  arena: &'a Bump,
+}
+
+pub struct CleanupEnableBackgroundPluginConfig {}
+
+impl<'a> CleanupEnableBackgroundPlugin<'a> {
+  pub fn new(_config: CleanupEnableBackgroundPluginConfig, arena: &'a Bump) -> Self {
+    CleanupEnableBackgroundPlugin {
+      uses_background: false,
+      enable_background: Regex::new(
+        r"^new\s0\s0\s([-+]?\d*\.?\d+(?:[eE][-+]?\d+)?)\s([-+]?\d*\.?\d+(?:[eE][-+]?\d+)?)$",
+      )
+      .unwrap(),
+      arena,
+    }
+  }
+
+  /// 删除 `style` 中的 `enable-background` 声明
+  fn remove_from_style(&self, el: &mut XMLAstElement<'a>) {
+    let Some(style) = el.get_attribute("style") else {
+      return;
+    };
+    let declarations: Vec<&str> = style
+      .split(';')
+      .filter(|declaration| {
+        declaration
+          .split_once(':')
+          .is_none_or(|(name, _)| name.trim() != "enable-background")
+      })
+      .collect();
+    let new_style = declarations.join(";");
+    if new_style.trim().is_empty() {
+      el.remove_attribute("style");
+    } else if new_style.len() != style.len() {
+      el.set_attribute("style", self.arena.alloc_str(&new_style));
+    }
+  }
+}
+
+impl<'a> Plugin<'a> for CleanupEnableBackgroundPlugin<'a> {
+  fn root_enter(&mut self, root: &mut XMLAstRoot<'a>) {
+    self.uses_background = uses_background(&root.children);
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    if !self.uses_background {
+      el.remove_attribute("enable-background");
+      self.remove_from_style(el);
+      return VisitAction::Keep;
+    }
+
+    let Some(value) = el.get_attribute("enable-background") else {
+      return VisitAction::Keep;
+    };
+    if !matches!(el.name, "svg" | "mask" | "pattern") {
+      return VisitAction::Keep;
+    }
+    let (Some(width), Some(height)) = (el.get_attribute("width"), el.get_attribute("height"))
+    else {
+      return VisitAction::Keep;
+    };
+    let matches_size = self
+      .enable_background
+      .captures(value.trim())
+      .is_some_and(|caps| &caps[1] == width && &caps[2] == height);
+    if matches_size {
+      if el.name == "svg" {
+        el.remove_attribute("enable-background");
+      } else {
+        el.set_attribute("enable-background", "new");
+      }
+    }
+    VisitAction::Keep
+  }
+}
+
+/// 是否有滤镜使用了背景图像
+fn uses_background(children: &[XMLAstChild]) -> bool {
+  children.iter().any(|child| match child {
+    XMLAstChild::Element(el) => {
+      el.attributes.iter().any(|(name, value)| {
+        matches!(*name, "in" | "in2") && matches!(*value, "BackgroundImage" | "BackgroundAlpha")
+      }) || uses_background(&el.children)
+    }
+    _ => false,
+  })
+}
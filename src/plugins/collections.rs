@@ -0,0 +1,152 @@
+//! 多个插件共用的元素和属性分组，与 svgo 的 `_collections.js` 对应
+use phf::{phf_set, Set};
+
+/// 可以被子元素继承的属性
+pub static INHERITABLE_ATTRS: Set<&'static str> = phf_set! {
+  "clip-rule",
+  "color-interpolation-filters",
+  "color-interpolation",
+  "color-profile",
+  "color-rendering",
+  "color",
+  "cursor",
+  "direction",
+  "dominant-baseline",
+  "fill-opacity",
+  "fill-rule",
+  "fill",
+  "font-family",
+  "font-size-adjust",
+  "font-size",
+  "font-stretch",
+  "font-style",
+  "font-variant",
+  "font-weight",
+  "font",
+  "glyph-orientation-horizontal",
+  "glyph-orientation-vertical",
+  "image-rendering",
+  "letter-spacing",
+  "marker-end",
+  "marker-mid",
+  "marker-start",
+  "marker",
+  "paint-order",
+  "pointer-events",
+  "shape-rendering",
+  "stroke-dasharray",
+  "stroke-dashoffset",
+  "stroke-linecap",
+  "stroke-linejoin",
+  "stroke-miterlimit",
+  "stroke-opacity",
+  "stroke-width",
+  "stroke",
+  "text-anchor",
+  "text-rendering",
+  "transform",
+  "visibility",
+  "word-spacing",
+  "writing-mode",
+};
+
+/// 不会被继承、但作用在 `<g>` 上仍然有意义的表现属性
+pub static PRESENTATION_NON_INHERITABLE_GROUP_ATTRS: Set<&'static str> = phf_set! {
+  "clip-path",
+  "display",
+  "filter",
+  "mask",
+  "opacity",
+  "text-decoration",
+  "transform",
+  "unicode-bidi",
+};
+
+/// 所有表现属性
+pub static PRESENTATION_ATTRS: Set<&'static str> = phf_set! {
+  "alignment-baseline",
+  "baseline-shift",
+  "clip-path",
+  "clip-rule",
+  "clip",
+  "color-interpolation-filters",
+  "color-interpolation",
+  "color-profile",
+  "color-rendering",
+  "color",
+  "cursor",
+  "direction",
+  "display",
+  "dominant-baseline",
+  "enable-background",
+  "fill-opacity",
+  "fill-rule",
+  "fill",
+  "filter",
+  "flood-color",
+  "flood-opacity",
+  "font-family",
+  "font-size-adjust",
+  "font-size",
+  "font-stretch",
+  "font-style",
+  "font-variant",
+  "font-weight",
+  "glyph-orientation-horizontal",
+  "glyph-orientation-vertical",
+  "image-rendering",
+  "letter-spacing",
+  "lighting-color",
+  "marker-end",
+  "marker-mid",
+  "marker-start",
+  "mask",
+  "opacity",
+  "overflow",
+  "paint-order",
+  "pointer-events",
+  "shape-rendering",
+  "stop-color",
+  "stop-opacity",
+  "stroke-dasharray",
+  "stroke-dashoffset",
+  "stroke-linecap",
+  "stroke-linejoin",
+  "stroke-miterlimit",
+  "stroke-opacity",
+  "stroke-width",
+  "stroke",
+  "text-anchor",
+  "text-decoration",
+  "text-overflow",
+  "text-rendering",
+  "transform",
+  "transform-origin",
+  "unicode-bidi",
+  "vector-effect",
+  "visibility",
+  "white-space",
+  "word-spacing",
+  "writing-mode",
+};
+
+/// 可以直接应用 `transform` 的路径类元素
+pub static PATH_ELEMS: Set<&'static str> = phf_set! {
+  "glyph",
+  "missing-glyph",
+  "path",
+};
+
+/// 值中可能包含 `url(#...)` 引用的属性
+pub static REFERENCES_PROPS: Set<&'static str> = phf_set! {
+  "clip-path",
+  "color-profile",
+  "fill",
+  "filter",
+  "marker-end",
+  "marker-mid",
+  "marker-start",
+  "mask",
+  "stroke",
+  "style",
+};
@@ -0,0 +1,277 @@
+use std::collections::{HashMap, HashSet};
+
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+use regex::Regex;
+
+use crate::optimizer::Plugin;
+use crate::parser::{XMLAstChild, XMLAstElement, XMLAstRoot};
+
+/// Converts one-stop (single color) gradients to a plain color.
+///
+/// ```svg
+/// <defs>
+///   <linearGradient id="a"><stop stop-color="#ddc4cc"/></linearGradient>
+/// </defs>
+/// <rect fill="url(#a)" style="stroke: url(#a)"/>
+///              ⬇
+/// <rect fill="#ddc4cc" style="stroke:#ddc4cc"/>
+/// ```
+///
+/// 只有一个 `<stop>`（或通过 `href` 继承了只有一个 `<stop>` 的模板）的渐变会被替换为纯色，
+/// 替换后不再被引用的渐变会被删除。`stop-opacity` 不为 1 的渐变无法用颜色表示，保持不变。
+pub struct ConvertOneStopGradientsPlugin<'a> {
+  url_re: Regex,
+  arena: &'a Bump,
+}
+
+pub struct ConvertOneStopGradientsPluginConfig {}
+
+/// 渐变的 `href` 最多跟随的层数，避免循环引用
+const MAX_HREF_DEPTH: usize = 16;
+
+struct Gradient<'a> {
+  href: Option<&'a str>,
+  /// 每个 `<stop>` 的 `stop-color` 和 `stop-opacity`
+  stops: Vec<(Option<&'a str>, Option<&'a str>)>,
+}
+
+impl<'a> ConvertOneStopGradientsPlugin<'a> {
+  pub fn new(_config: ConvertOneStopGradientsPluginConfig, arena: &'a Bump) -> Self {
+    ConvertOneStopGradientsPlugin {
+      url_re: Regex::new(r#"^url\(\s*(["']?)#([^"')\s]+)(["']?)\s*\)$"#).unwrap(),
+      arena,
+    }
+  }
+
+  /// `value` 是否恰好为 `url(#id)`，返回其中的 id
+  fn url_id<'v>(&self, value: &'v str) -> Option<&'v str> {
+    self
+      .url_re
+      .captures(value.trim())
+      .and_then(|caps| caps.get(2))
+      .map(|id| id.as_str())
+  }
+
+  fn replace_references(
+    &self,
+    children: &mut BumpVec<'a, XMLAstChild<'a>>,
+    colors: &HashMap<&'a str, &'a str>,
+  ) {
+    for child in children.iter_mut() {
+      let XMLAstChild::Element(el) = child else {
+        continue;
+      };
+      for (name, value) in el.attributes.iter_mut() {
+        match *name {
+          "fill" | "stroke" => {
+            if let Some(color) = self.url_id(value).and_then(|id| colors.get(id)) {
+              *value = color;
+            }
+          }
+          "style" => {
+            if let Some(style) = self.replace_in_style(value, colors) {
+              *value = style;
+            }
+          }
+          _ => {}
+        }
+      }
+      self.replace_references(&mut el.children, colors);
+    }
+  }
+
+  fn replace_in_style(&self, style: &str, colors: &HashMap<&'a str, &'a str>) -> Option<&'a str> {
+    let mut changed = false;
+    let declarations: Vec<String> = style
+      .split(';')
+      .map(|declaration| {
+        let replaced = declaration.split_once(':').and_then(|(name, value)| {
+          let name = name.trim();
+          if name != "fill" && name != "stroke" {
+            return None;
+          }
+          let (value, important) = match value.trim().strip_suffix("!important") {
+            Some(value) => (value, "!important"),
+            None => (value, ""),
+          };
+          let color = colors.get(self.url_id(value)?)?;
+          Some(format!("{}:{}{}", name, color, important))
+        });
+        changed |= replaced.is_some();
+        replaced.unwrap_or_else(|| declaration.to_string())
+      })
+      .collect();
+    changed.then(|| self.arena.alloc_str(&declarations.join(";")) as &str)
+  }
+
+  /// 收集所有被 `url(#...)`、`href` 或 `<style>` 引用的 id
+  fn collect_references(&self, children: &[XMLAstChild<'a>], referenced: &mut HashSet<&'a str>) {
+    for child in children {
+      let XMLAstChild::Element(el) = child else {
+        continue;
+      };
+      for (name, value) in el.attributes.iter() {
+        if *name == "href" || name.ends_with(":href") {
+          if let Some(id) = value.trim().strip_prefix('#') {
+            referenced.insert(id);
+          }
+        }
+        let mut rest = *value;
+        while let Some(start) = rest.find("url(") {
+          rest = &rest[start..];
+          let end = rest.find(')').map_or(rest.len(), |end| end + 1);
+          if let Some(id) = self.url_id(&rest[..end]) {
+            referenced.insert(id);
+          }
+          rest = &rest[end..];
+        }
+      }
+      if el.name == "style" {
+        // 样式表中的引用不做解析，出现过的 id 都视为被引用
+        for child in el.children.iter() {
+          let text = match child {
+            XMLAstChild::Text(text) => text.value,
+            XMLAstChild::Cdata(cdata) => cdata.value,
+            _ => continue,
+          };
+          referenced.extend(
+            text
+              .split(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
+              .filter(|word| !word.is_empty()),
+          );
+        }
+      }
+      self.collect_references(&el.children, referenced);
+    }
+  }
+}
+
+impl<'a> Plugin<'a> for ConvertOneStopGradientsPlugin<'a> {
+  fn root_exit(&mut self, root: &mut XMLAstRoot<'a>) {
+    let mut gradients = HashMap::new();
+    collect_gradients(&root.children, &mut gradients);
+
+    let mut colors: HashMap<&'a str, &'a str> = HashMap::new();
+    for id in gradients.keys() {
+      let Some(stops) = resolve_stops(&gradients, id) else {
+        continue;
+      };
+      if let [(color, opacity)] = stops[..] {
+        if opacity.is_some_and(|opacity| opacity.parse::<f64>().ok() != Some(1.0)) {
+          continue;
+        }
+        colors.insert(id, color.unwrap_or("#000"));
+      }
+    }
+    if colors.is_empty() {
+      return;
+    }
+    self.replace_references(&mut root.children, &colors);
+
+    // 删除一个渐变后，它引用的模板可能也不再被引用
+    loop {
+      let mut referenced = HashSet::new();
+      self.collect_references(&root.children, &mut referenced);
+      let unused: HashSet<&str> =
+        colors.keys().copied().filter(|id| !referenced.contains(id)).collect();
+      if !remove_gradients(&mut root.children, &unused) {
+        break;
+      }
+      colors.retain(|id, _| !unused.contains(id));
+    }
+  }
+}
+
+fn is_gradient(el: &XMLAstElement) -> bool {
+  el.name == "linearGradient" || el.name == "radialGradient"
+}
+
+fn collect_gradients<'a>(
+  children: &[XMLAstChild<'a>],
+  gradients: &mut HashMap<&'a str, Gradient<'a>>,
+) {
+  for child in children {
+    let XMLAstChild::Element(el) = child else {
+      continue;
+    };
+    if let (true, Some(id)) = (is_gradient(el), el.get_attribute("id")) {
+      let href = el
+        .attributes
+        .iter()
+        .find(|(name, _)| *name == "href" || *name == "xlink:href")
+        .and_then(|(_, value)| value.trim().strip_prefix('#'));
+      let stops = el
+        .children
+        .iter()
+        .filter_map(|child| match child {
+          XMLAstChild::Element(stop) if stop.name == "stop" => Some(stop_color(stop)),
+          _ => None,
+        })
+        .collect();
+      gradients.insert(id, Gradient { href, stops });
+    }
+    collect_gradients(&el.children, gradients);
+  }
+}
+
+/// 读取 `<stop>` 的颜色和透明度，`style` 中的声明优先
+fn stop_color<'a>(stop: &XMLAstElement<'a>) -> (Option<&'a str>, Option<&'a str>) {
+  let mut color = stop.get_attribute("stop-color").map(str::trim);
+  let mut opacity = stop.get_attribute("stop-opacity").map(str::trim);
+  if let Some(style) = stop.get_attribute("style") {
+    for declaration in style.split(';') {
+      if let Some((name, value)) = declaration.split_once(':') {
+        let value = value.trim();
+        let value = value.strip_suffix("!important").unwrap_or(value).trim_end();
+        match name.trim() {
+          "stop-color" => color = Some(value),
+          "stop-opacity" => opacity = Some(value),
+          _ => {}
+        }
+      }
+    }
+  }
+  (color, opacity)
+}
+
+/// 没有 `<stop>` 的渐变使用 `href` 指向的渐变的 `<stop>`
+fn resolve_stops<'g, 'a>(
+  gradients: &'g HashMap<&'a str, Gradient<'a>>,
+  id: &str,
+) -> Option<&'g [(Option<&'a str>, Option<&'a str>)]> {
+  let mut gradient = gradients.get(id)?;
+  for _ in 0..MAX_HREF_DEPTH {
+    if !gradient.stops.is_empty() {
+      return Some(&gradient.stops);
+    }
+    gradient = gradients.get(gradient.href?)?;
+  }
+  None
+}
+
+/// 删除指定 id 的渐变，以及因此变空的 `<defs>`，返回是否有删除
+fn remove_gradients(children: &mut BumpVec<XMLAstChild>, ids: &HashSet<&str>) -> bool {
+  let mut removed = false;
+  children.retain_mut(|child| {
+    let XMLAstChild::Element(el) = child else {
+      return true;
+    };
+    if is_gradient(el) && el.get_attribute("id").is_some_and(|id| ids.contains(id)) {
+      removed = true;
+      return false;
+    }
+    if remove_gradients(&mut el.children, ids) {
+      removed = true;
+      if el.name == "defs" && el.children.iter().all(is_whitespace) {
+        return false;
+      }
+    }
+    true
+  });
+  removed
+}
+
+fn is_whitespace(child: &XMLAstChild) -> bool {
+  matches!(child, XMLAstChild::Text(text) if text.value.trim().is_empty())
+}
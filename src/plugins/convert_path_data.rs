@@ -0,0 +1,209 @@
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+
+use crate::optimizer::Plugin;
+use crate::parser::{XMLAstChild, XMLAstElement, XMLAstRoot};
+use crate::path_data::{
+  format_number, parse_complete_path_data, stringify_path_data, transform_path_data,
+};
+use crate::plugins::collections::REFERENCES_PROPS;
+use crate::transform::{parse_transform, Matrix};
+
+/// Optimizes path data.
+///
+/// 目前实现了 svgo 的 `applyTransforms`：把 `<path>` 的 `transform` 直接应用到 `d` 的坐标上。
+///
+/// ```svg
+/// <path transform="translate(10 20)" d="M0 0h10v10z"/>
+///              ⬇
+/// <path d="M10 20h10v10z"/>
+/// ```
+///
+/// 以下情况保持不变：
+/// - path 带有 `id`、`style` 或 `vector-effect`，或文档中存在 `<style>`
+/// - 填充、描边等属性（包括继承来的）引用了 `url(#...)`，例如 `userSpaceOnUse` 的渐变
+/// - 有描边时变换不是等比缩放；等比缩放时会同步调整 `stroke-width` 和虚线
+/// - 使用了 marker
+pub struct ConvertPathDataPlugin<'a> {
+  apply_transforms: bool,
+  apply_transforms_stroked: bool,
+  float_precision: usize,
+  arena: &'a Bump,
+}
+
+pub struct ConvertPathDataPluginConfig {
+  /// 把 `transform` 应用到路径坐标上，默认 `true`
+  pub apply_transforms: Option<bool>,
+  /// 有描边的路径也应用 `transform`，默认 `true`
+  pub apply_transforms_stroked: Option<bool>,
+  /// 输出坐标的小数位数，默认 `3`
+  pub float_precision: Option<u32>,
+}
+
+/// 从祖先元素继承的、影响能否应用 `transform` 的样式
+#[derive(Clone, Copy, Default)]
+struct Inherited<'a> {
+  stroke: Option<&'a str>,
+  stroke_width: Option<&'a str>,
+  stroke_dasharray: Option<&'a str>,
+  stroke_dashoffset: Option<&'a str>,
+  has_marker: bool,
+  has_paint_reference: bool,
+}
+
+impl<'a> ConvertPathDataPlugin<'a> {
+  pub fn new(config: ConvertPathDataPluginConfig, arena: &'a Bump) -> Self {
+    ConvertPathDataPlugin {
+      apply_transforms: config.apply_transforms.unwrap_or(true),
+      apply_transforms_stroked: config.apply_transforms_stroked.unwrap_or(true),
+      float_precision: config.float_precision.unwrap_or(3) as usize,
+      arena,
+    }
+  }
+
+  fn process_children(&self, children: &mut BumpVec<'a, XMLAstChild<'a>>, parent: &Inherited<'a>) {
+    for child in children.iter_mut() {
+      let XMLAstChild::Element(el) = child else {
+        continue;
+      };
+      let inherited = inherit(el, parent);
+      if el.name == "path" {
+        self.apply_transform(el, &inherited);
+      }
+      self.process_children(&mut el.children, &inherited);
+    }
+  }
+
+  fn apply_transform(&self, el: &mut XMLAstElement<'a>, style: &Inherited<'a>) {
+    let (Some(d), Some(transform)) = (el.get_attribute("d"), el.get_attribute("transform")) else {
+      return;
+    };
+    if el.has_attribute("id")
+      || el.has_attribute("style")
+      || el.has_attribute("vector-effect")
+      || style.has_marker
+      || style.has_paint_reference
+    {
+      return;
+    }
+    let Some(matrix) = parse_transform(transform) else {
+      return;
+    };
+    // 只解析了一部分的 `d` 改写后会丢失剩下的数据
+    let Some(path_data) = parse_complete_path_data(d) else {
+      return;
+    };
+
+    let mut stroke_attrs: Vec<(&'a str, &'a str)> = Vec::new();
+    if style.stroke.is_some_and(|stroke| stroke != "none") {
+      if !self.apply_transforms_stroked {
+        return;
+      }
+      let Some(scale) = uniform_scale(&matrix) else {
+        return;
+      };
+      if (scale - 1.0).abs() > 1e-9 {
+        let width = match style.stroke_width {
+          Some(width) => scale_lengths(width, scale, self.float_precision),
+          None => Some(format_number(scale, self.float_precision)),
+        };
+        let Some(width) = width else {
+          return;
+        };
+        stroke_attrs.push(("stroke-width", self.arena.alloc_str(&width)));
+        for (name, value) in [
+          ("stroke-dasharray", style.stroke_dasharray),
+          ("stroke-dashoffset", style.stroke_dashoffset),
+        ] {
+          let Some(value) = value.filter(|value| *value != "none") else {
+            continue;
+          };
+          let Some(scaled) = scale_lengths(value, scale, self.float_precision) else {
+            return;
+          };
+          stroke_attrs.push((name, self.arena.alloc_str(&scaled)));
+        }
+      }
+    }
+
+    let path_data = transform_path_data(&path_data, &matrix);
+    let new_d = stringify_path_data(&path_data, self.float_precision);
+    el.set_attribute("d", self.arena.alloc_str(&new_d));
+    el.remove_attribute("transform");
+    for (name, value) in stroke_attrs {
+      el.set_attribute(name, value);
+    }
+  }
+}
+
+impl<'a> Plugin<'a> for ConvertPathDataPlugin<'a> {
+  fn root_exit(&mut self, root: &mut XMLAstRoot<'a>) {
+    if !self.apply_transforms || has_style_element(&root.children) {
+      return;
+    }
+    self.process_children(&mut root.children, &Inherited::default());
+  }
+}
+
+/// 合并元素自身的表现属性和 `style` 声明
+fn inherit<'a>(el: &XMLAstElement<'a>, parent: &Inherited<'a>) -> Inherited<'a> {
+  let mut style = *parent;
+  let mut apply = |name: &str, value: &'a str| match name {
+    "stroke" => style.stroke = Some(value),
+    "stroke-width" => style.stroke_width = Some(value),
+    "stroke-dasharray" => style.stroke_dasharray = Some(value),
+    "stroke-dashoffset" => style.stroke_dashoffset = Some(value),
+    "marker" | "marker-start" | "marker-mid" | "marker-end" => {
+      style.has_marker |= value != "none";
+    }
+    _ => {}
+  };
+  for (name, value) in el.attributes.iter() {
+    apply(name, value.trim());
+  }
+  if let Some(style_attr) = el.get_attribute("style") {
+    for declaration in style_attr.split(';') {
+      if let Some((name, value)) = declaration.split_once(':') {
+        let value = value.trim();
+        apply(
+          name.trim(),
+          value.strip_suffix("!important").unwrap_or(value).trim_end(),
+        );
+      }
+    }
+  }
+  // 引用的内容（如 userSpaceOnUse 的渐变）使用路径所在的坐标系
+  style.has_paint_reference |= el
+    .attributes
+    .iter()
+    .any(|(name, value)| REFERENCES_PROPS.contains(name) && value.contains("url("));
+  style
+}
+
+/// 等比缩放（可以带旋转和镜像）时返回缩放比例
+fn uniform_scale(matrix: &Matrix) -> Option<f64> {
+  let Matrix { a, b, c, d, .. } = *matrix;
+  let close = |x: f64, y: f64| (x - y).abs() < 1e-9;
+  let uniform = (close(a, d) && close(b, -c)) || (close(a, -d) && close(b, c));
+  uniform.then(|| a.hypot(b))
+}
+
+/// 按比例缩放空格或逗号分隔的长度列表，不支持带单位的值
+fn scale_lengths(value: &str, scale: f64, precision: usize) -> Option<String> {
+  let scaled: Option<Vec<String>> = value
+    .split(|c: char| c == ',' || c.is_whitespace())
+    .filter(|part| !part.is_empty())
+    .map(|part| {
+      let number = part.strip_suffix("px").unwrap_or(part).parse::<f64>().ok()?;
+      Some(format_number(number * scale, precision))
+    })
+    .collect();
+  scaled.filter(|parts| !parts.is_empty()).map(|parts| parts.join(" "))
+}
+
+fn has_style_element(children: &[XMLAstChild]) -> bool {
+  children.iter().any(|child| match child {
+    XMLAstChild::Element(el) => el.name == "style" || has_style_element(&el.children),
+    _ => false,
+  })
+}
@@ -0,0 +1,187 @@
+use std::collections::{HashMap, HashSet};
+
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+use regex::{Captures, Regex};
+
+use crate::optimizer::Plugin;
+use crate::parser::{XMLAstChild, XMLAstElement, XMLAstRoot};
+
+/// Merges gradients with identical attributes and stops, rewriting every
+/// `url(#...)` and `href` reference to the first one.
+///
+/// ```svg
+/// <linearGradient id="a"><stop offset="0" stop-color="red"/></linearGradient>
+/// <linearGradient id="b"><stop offset="0" stop-color="red"/></linearGradient>
+/// <rect fill="url(#b)"/>
+///              ⬇
+/// <linearGradient id="a"><stop offset="0" stop-color="red"/></linearGradient>
+/// <rect fill="url(#a)"/>
+/// ```
+///
+/// 属性的顺序不影响比较。合并后通过 `href` 引用它们的渐变也可能变得相同，会继续合并。
+/// 在 `<style>` 或 `<script>` 中出现过的 id 可能被样式表或脚本使用，不会被合并掉。
+pub struct MergeGradientsPlugin<'a> {
+  url_re: Regex,
+  arena: &'a Bump,
+}
+
+pub struct MergeGradientsPluginConfig {}
+
+impl<'a> MergeGradientsPlugin<'a> {
+  pub fn new(_config: MergeGradientsPluginConfig, arena: &'a Bump) -> Self {
+    MergeGradientsPlugin {
+      url_re: Regex::new(r#"url\(\s*(["']?)#([^"')\s]+)(["']?)\s*\)"#).unwrap(),
+      arena,
+    }
+  }
+
+  fn rewrite_references(
+    &self,
+    children: &mut BumpVec<'a, XMLAstChild<'a>>,
+    merged: &HashMap<&'a str, &'a str>,
+  ) {
+    for child in children.iter_mut() {
+      let XMLAstChild::Element(el) = child else {
+        continue;
+      };
+      for (name, value) in el.attributes.iter_mut() {
+        if *name == "href" || name.ends_with(":href") {
+          if let Some(target) = value.trim().strip_prefix('#').and_then(|id| merged.get(id)) {
+            *value = bumpalo::format!(in self.arena, "#{}", target).into_bump_str();
+          }
+          continue;
+        }
+        if !value.contains("url(") {
+          continue;
+        }
+        let mut changed = false;
+        let replaced =
+          self.url_re.replace_all(value, |caps: &Captures| match merged.get(&caps[2]) {
+            Some(target) => {
+              changed = true;
+              format!("url({}#{}{})", &caps[1], target, &caps[3])
+            }
+            None => caps[0].to_string(),
+          });
+        if changed {
+          *value = self.arena.alloc_str(&replaced);
+        }
+      }
+      self.rewrite_references(&mut el.children, merged);
+    }
+  }
+}
+
+impl<'a> Plugin<'a> for MergeGradientsPlugin<'a> {
+  fn root_exit(&mut self, root: &mut XMLAstRoot<'a>) {
+    let mut scripts = Vec::new();
+    collect_style_and_script_text(&root.children, &mut scripts);
+
+    loop {
+      let mut gradients = Vec::new();
+      collect_gradients(&root.children, &mut gradients);
+
+      // 相同的渐变都合并到第一个上
+      let mut first_by_key: HashMap<String, &'a str> = HashMap::new();
+      let mut merged: HashMap<&'a str, &'a str> = HashMap::new();
+      for (id, key) in gradients {
+        match first_by_key.get(&key) {
+          // id 重复的渐变不能合并，按 id 删除时会把保留的那个也删掉
+          Some(&target) if target == id => {}
+          Some(&target) if !scripts.iter().any(|text| text.contains(id)) => {
+            merged.insert(id, target);
+          }
+          Some(_) => {}
+          None => {
+            first_by_key.insert(key, id);
+          }
+        }
+      }
+      if merged.is_empty() {
+        break;
+      }
+
+      self.rewrite_references(&mut root.children, &merged);
+      let ids: HashSet<&str> = merged.keys().copied().collect();
+      remove_gradients(&mut root.children, &ids);
+    }
+  }
+}
+
+fn is_gradient(el: &XMLAstElement) -> bool {
+  el.name == "linearGradient" || el.name == "radialGradient"
+}
+
+/// 收集带 id 的渐变及其比较用的 key，按文档顺序排列
+fn collect_gradients<'a>(children: &[XMLAstChild<'a>], gradients: &mut Vec<(&'a str, String)>) {
+  for child in children {
+    let XMLAstChild::Element(el) = child else {
+      continue;
+    };
+    if is_gradient(el) {
+      if let (Some(id), Some(key)) = (el.get_attribute("id"), gradient_key(el)) {
+        gradients.push((id, key));
+      }
+    }
+    collect_gradients(&el.children, gradients);
+  }
+}
+
+/// 由标签名、除 id 外的属性和 `<stop>` 组成的 key，包含其它子元素时返回 `None`
+fn gradient_key(el: &XMLAstElement) -> Option<String> {
+  let mut key = String::from(el.name);
+  push_attributes(&mut key, el, true);
+  for child in el.children.iter() {
+    match child {
+      XMLAstChild::Element(stop) if stop.name == "stop" && stop.children.is_empty() => {
+        key.push_str("<stop");
+        push_attributes(&mut key, stop, false);
+      }
+      XMLAstChild::Text(text) if text.value.trim().is_empty() => {}
+      XMLAstChild::Comment(_) => {}
+      _ => return None,
+    }
+  }
+  Some(key)
+}
+
+fn push_attributes(key: &mut String, el: &XMLAstElement, skip_id: bool) {
+  let mut attributes: Vec<_> =
+    el.attributes.iter().filter(|(name, _)| !(skip_id && *name == "id")).collect();
+  attributes.sort();
+  for (name, value) in attributes {
+    key.push_str(&format!(" {}=\"{}\"", name, value));
+  }
+}
+
+fn collect_style_and_script_text<'a>(children: &[XMLAstChild<'a>], texts: &mut Vec<&'a str>) {
+  for child in children {
+    let XMLAstChild::Element(el) = child else {
+      continue;
+    };
+    if el.name == "style" || el.name == "script" {
+      for child in el.children.iter() {
+        match child {
+          XMLAstChild::Text(text) => texts.push(text.value),
+          XMLAstChild::Cdata(cdata) => texts.push(cdata.value),
+          _ => {}
+        }
+      }
+    }
+    collect_style_and_script_text(&el.children, texts);
+  }
+}
+
+fn remove_gradients(children: &mut BumpVec<XMLAstChild>, ids: &HashSet<&str>) {
+  children.retain_mut(|child| {
+    let XMLAstChild::Element(el) = child else {
+      return true;
+    };
+    if is_gradient(el) && el.get_attribute("id").is_some_and(|id| ids.contains(id)) {
+      return false;
+    }
+    remove_gradients(&mut el.children, ids);
+    true
+  });
+}
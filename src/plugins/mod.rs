@@ -1,8 +1,37 @@
+pub mod add_attributes_to_svg_element;
+pub mod add_classes_to_svg_element;
+pub mod cleanup_attrs;
+pub mod cleanup_enable_background;
+pub mod collections;
+pub mod convert_one_stop_gradients;
+pub mod convert_path_data;
+pub mod merge_gradients;
 pub mod move_elems_attrs_to_group;
+pub mod move_group_attrs_to_elems;
+pub mod prefix_ids;
+pub mod registry;
+pub mod remove_attrs;
 pub mod remove_comments;
 pub mod remove_desc;
+pub mod remove_dimensions;
 pub mod remove_doctype;
 pub mod remove_editors_ns_data;
+pub mod remove_elements_by_attr;
+pub mod remove_empty_attrs;
+pub mod remove_empty_text;
 pub mod remove_metadata;
+pub mod remove_non_inheritable_group_attrs;
+pub mod remove_off_canvas_paths;
+pub mod remove_raster_images;
+pub mod remove_scripts;
+pub mod remove_style_element;
 pub mod remove_title;
+pub mod remove_unused_ns;
+pub mod remove_useless_stroke_and_fill;
+pub mod remove_view_box;
+pub mod remove_xlink;
 pub mod remove_xml_proc_inst;
+pub mod remove_xmlns;
+pub mod reuse_paths;
+pub mod sort_attrs;
+pub mod sort_defs_children;
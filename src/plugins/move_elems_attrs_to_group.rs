@@ -1,9 +1,9 @@
 use bumpalo::Bump;
 
-use crate::optimizer::Plugin;
+use crate::optimizer::{Plugin, VisitContext};
 use crate::parser::{XMLAstChild, XMLAstElement};
+use crate::plugins::collections::{INHERITABLE_ATTRS, PATH_ELEMS};
 use bumpalo::collections::Vec as BumpVec;
-use phf::{phf_set, Set};
 
 /// Move common attributes of group children to the group
 ///
@@ -40,68 +40,10 @@ impl<'a> MoveElemsAttrsToGroupPlugin<'a> {
   }
 }
 
-// Collection of inheritable attributes that can be moved up to parent groups
-static INHERITABLE_ATTRS: Set<&'static str> = phf_set! {
-  "clip-rule",
-  "color-interpolation-filters",
-  "color-interpolation",
-  "color-profile",
-  "color-rendering",
-  "color",
-  "cursor",
-  "direction",
-  "dominant-baseline",
-  "fill-opacity",
-  "fill-rule",
-  "fill",
-  "font-family",
-  "font-size-adjust",
-  "font-size",
-  "font-stretch",
-  "font-style",
-  "font-variant",
-  "font-weight",
-  "font",
-  "glyph-orientation-horizontal",
-  "glyph-orientation-vertical",
-  "image-rendering",
-  "letter-spacing",
-  "marker-end",
-  "marker-mid",
-  "marker-start",
-  "marker",
-  "paint-order",
-  "pointer-events",
-  "shape-rendering",
-  "stroke-dasharray",
-  "stroke-dashoffset",
-  "stroke-linecap",
-  "stroke-linejoin",
-  "stroke-miterlimit",
-  "stroke-opacity",
-  "stroke-width",
-  "stroke",
-  "text-anchor",
-  "text-rendering",
-  "transform",
-  "visibility",
-  "word-spacing",
-  "writing-mode",
-};
-
-static PATH_ELEMS: Set<&'static str> = phf_set! {
-  "clip-path",
-  "display",
-  "filter",
-  "mask",
-  "opacity",
-  "text-decoration",
-  "transform",
-  "unicode-bidi",
-};
-
 impl<'a> Plugin<'a> for MoveElemsAttrsToGroupPlugin<'a> {
-  fn root_enter(&self, _el: &mut crate::parser::XMLAstRoot<'a>) {
+  fn root_enter(&mut self, _el: &mut crate::parser::XMLAstRoot<'a>) {
+    // 文档中任何位置的 <style> 都会影响所有 <g>，包括出现在它之后的，而 VisitContext
+    // 只能看到祖先和兄弟节点，所以在遍历开始前扫描整棵树
     let mut element_stack: Vec<&XMLAstChild<'a>> = Vec::new();
     for child in _el.children.iter() {
       element_stack.push(child);
@@ -112,10 +54,7 @@ impl<'a> Plugin<'a> for MoveElemsAttrsToGroupPlugin<'a> {
       match element {
         XMLAstChild::Element(_el) => {
           if _el.name == "style" {
-            let this = self as *const _ as *mut MoveElemsAttrsToGroupPlugin;
-            unsafe {
-              (*this).has_style_element = true;
-            }
+            self.has_style_element = true;
             break;
           } else {
             for child in _el.children.iter() {
@@ -129,7 +68,7 @@ impl<'a> Plugin<'a> for MoveElemsAttrsToGroupPlugin<'a> {
     }
   }
 
-  fn element_exit(&self, el: &mut XMLAstElement<'a>) {
+  fn element_exit(&mut self, el: &mut XMLAstElement<'a>, _ctx: &VisitContext<'_, 'a>) {
     // Process only groups with more than 1 child
     if el.name != "g" || el.children.len() <= 1 {
       return;
@@ -165,23 +104,12 @@ impl<'a> Plugin<'a> for MoveElemsAttrsToGroupPlugin<'a> {
           common_attributes = Some(attrs);
         } else if let Some(ref mut attrs) = common_attributes {
           // Remove attributes that aren't common
-          let mut to_remove = Vec::new();
-          for (index, (name, value)) in attrs.iter().enumerate() {
-            let mut found = false;
-            for (child_name, child_value) in &child_el.attributes {
-              if *name == *child_name && *value == *child_value {
-                found = true;
-                break;
-              }
-            }
-            if !found {
-              to_remove.push(index);
-            }
-          }
-          // Remove non-common attributes
-          for remove_index in to_remove {
-            attrs.remove(remove_index);
-          }
+          attrs.retain(|(name, value)| {
+            child_el
+              .attributes
+              .iter()
+              .any(|(child_name, child_value)| *name == *child_name && *value == *child_value)
+          });
         }
       }
     }
@@ -222,22 +150,8 @@ impl<'a> Plugin<'a> for MoveElemsAttrsToGroupPlugin<'a> {
             el.attributes.push((allocated_name, allocated_value));
           }
         } else {
-          // Check if attribute already exists in parent
-          let mut exists = false;
-          for (attr_name, _) in &el.attributes {
-            if *attr_name == *name {
-              // Dereference attr_name and name for comparison
-              exists = true;
-              break;
-            }
-          }
-          // Add attribute if not already present
-          if !exists {
-            // Allocate name and value in the arena before pushing
-            let allocated_name = bumpalo::format!(in self.arena, "{}", name).into_bump_str();
-            let allocated_value = bumpalo::format!(in self.arena, "{}", value).into_bump_str();
-            el.attributes.push((allocated_name, allocated_value));
-          }
+          // Children override the group's value, so the common value wins
+          el.set_attribute(name, value);
         }
       }
     }
@@ -0,0 +1,77 @@
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::{XMLAstChild, XMLAstElement};
+use crate::plugins::collections::{PATH_ELEMS, REFERENCES_PROPS};
+
+/// Move some group attributes to the contained elements.
+///
+/// ```svg
+/// <g transform="scale(2)">
+///   <path transform="rotate(45)" d="M0,0 L10,20"/>
+///   <path transform="translate(10, 20)" d="M0,10 L20,30"/>
+/// </g>
+///              ⬇
+/// <g>
+///   <path transform="scale(2) rotate(45)" d="M0,0 L10,20"/>
+///   <path transform="scale(2) translate(10, 20)" d="M0,10 L20,30"/>
+/// </g>
+/// ```
+///
+/// 目前只移动 `transform`，之后可以由路径插件把它直接应用到坐标上。
+/// 子元素必须都是路径、`<g>` 或 `<text>` 且没有 `id`，
+/// 并且分组上没有 `url(#...)` 引用（引用内容的坐标系取决于分组的 `transform`）。
+pub struct MoveGroupAttrsToElemsPlugin<'a> {
+  arena: &'a Bump,
+}
+
+pub struct MoveGroupAttrsToElemsPluginConfig {}
+
+impl<'a> MoveGroupAttrsToElemsPlugin<'a> {
+  pub fn new(_config: MoveGroupAttrsToElemsPluginConfig, arena: &'a Bump) -> Self {
+    MoveGroupAttrsToElemsPlugin { arena }
+  }
+}
+
+impl<'a> Plugin<'a> for MoveGroupAttrsToElemsPlugin<'a> {
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    if el.name != "g" || el.children.is_empty() {
+      return VisitAction::Keep;
+    }
+    let Some(transform) = el.get_attribute("transform") else {
+      return VisitAction::Keep;
+    };
+    let has_references = el
+      .attributes
+      .iter()
+      .any(|(name, value)| REFERENCES_PROPS.contains(name) && value.contains("url("));
+    let movable = el.children.iter().all(|child| match child {
+      XMLAstChild::Element(child) => {
+        (PATH_ELEMS.contains(child.name) || child.name == "g" || child.name == "text")
+          && !child.has_attribute("id")
+      }
+      XMLAstChild::Text(text) => text.value.trim().is_empty(),
+      XMLAstChild::Comment(_) => true,
+      _ => false,
+    });
+    if has_references || !movable {
+      return VisitAction::Keep;
+    }
+
+    for child in el.children.iter_mut() {
+      if let XMLAstChild::Element(child) = child {
+        let value = match child.get_attribute("transform") {
+          Some(own) => bumpalo::format!(in self.arena, "{} {}", transform, own).into_bump_str(),
+          None => transform,
+        };
+        child.set_attribute("transform", value);
+      }
+    }
+    el.remove_attribute("transform");
+    VisitAction::Keep
+  }
+}
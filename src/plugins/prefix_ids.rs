@@ -0,0 +1,256 @@
+use bumpalo::Bump;
+use regex::{Captures, Regex};
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::{XMLAstChild, XMLAstElement};
+
+/// Prefix element IDs and class names with a prefix so that multiple SVGs can be
+/// inlined in one HTML page without collisions.
+///
+/// ```svg
+/// <svg>
+///   <style>.icon { fill: url(#grad) }</style>
+///   <linearGradient id="grad"/>
+///   <use href="#grad" class="icon"/>
+/// </svg>
+///              ⬇
+/// <svg>
+///   <style>.prefix__icon { fill: url(#prefix__grad) }</style>
+///   <linearGradient id="prefix__grad"/>
+///   <use href="#prefix__grad" class="prefix__icon"/>
+/// </svg>
+/// ```
+pub struct PrefixIdsPlugin<'a> {
+  delim: String,
+  prefix: PrefixIdsPrefix<'a>,
+  prefix_ids: bool,
+  prefix_class_names: bool,
+  path: Option<String>,
+  url_re: Regex,
+  arena: &'a Bump,
+}
+
+/// 前缀的来源
+pub enum PrefixIdsPrefix<'a> {
+  /// 由文件名生成，没有文件名时使用 `prefix`
+  Default,
+  /// 不加前缀（svgo 中的 `prefix: false`）
+  Disabled,
+  Static(String),
+  /// 每个元素调用一次回调生成前缀
+  Dynamic(PrefixGenerator<'a>),
+}
+
+/// 参数为当前元素和调用方传入的文件路径
+pub type PrefixGenerator<'a> = Box<dyn Fn(&XMLAstElement<'a>, Option<&str>) -> String + 'a>;
+
+pub struct PrefixIdsPluginConfig<'a> {
+  /// 前缀与原名之间的分隔符，默认 `__`
+  pub delim: Option<String>,
+  pub prefix: PrefixIdsPrefix<'a>,
+  /// 是否给 `id` 及其引用加前缀，默认 `true`
+  pub prefix_ids: Option<bool>,
+  /// 是否给 `class` 加前缀，默认 `true`
+  pub prefix_class_names: Option<bool>,
+  /// 调用方传入的文件路径，用于生成默认前缀
+  pub path: Option<String>,
+}
+
+impl<'a> PrefixIdsPlugin<'a> {
+  pub fn new(config: PrefixIdsPluginConfig<'a>, arena: &'a Bump) -> Self {
+    PrefixIdsPlugin {
+      delim: config.delim.unwrap_or_else(|| "__".to_string()),
+      prefix: config.prefix,
+      prefix_ids: config.prefix_ids.unwrap_or(true),
+      prefix_class_names: config.prefix_class_names.unwrap_or(true),
+      path: config.path,
+      url_re: Regex::new(r#"url\(\s*(["']?)#([^"')\s]+)(["']?)\s*\)"#).unwrap(),
+      arena,
+    }
+  }
+
+  /// 生成带分隔符的完整前缀，`prefix: false` 时返回空字符串
+  fn generate_prefix(&self, el: &XMLAstElement<'a>) -> String {
+    match &self.prefix {
+      PrefixIdsPrefix::Disabled => String::new(),
+      PrefixIdsPrefix::Static(prefix) => format!("{}{}", prefix, self.delim),
+      PrefixIdsPrefix::Dynamic(generate) => {
+        format!("{}{}", generate(el, self.path.as_deref()), self.delim)
+      }
+      PrefixIdsPrefix::Default => match self.path.as_deref().and_then(get_basename) {
+        Some(basename) => format!("{}{}", escape_identifier_name(basename), self.delim),
+        None => format!("prefix{}", self.delim),
+      },
+    }
+  }
+
+  fn prefix_urls(&self, value: &str, prefix: &str) -> Option<String> {
+    if !value.contains("url(") {
+      return None;
+    }
+    let replaced = self.url_re.replace_all(value, |caps: &Captures| {
+      format!(
+        "url({}#{}{})",
+        &caps[1],
+        prefix_id(prefix, &caps[2]),
+        &caps[3]
+      )
+    });
+    Some(replaced.into_owned())
+  }
+
+  /// 给 `<style>` 中的选择器和 `url(#...)` 加前缀
+  fn prefix_css(&self, css: &str, prefix: &str) -> String {
+    let mut output = String::with_capacity(css.len());
+    let mut segment_start = 0;
+    for (index, c) in css.char_indices() {
+      if c != '{' && c != '}' {
+        continue;
+      }
+      let segment = &css[segment_start..index];
+      if c == '{' {
+        // 大括号前是选择器或 at-rule 的 prelude
+        if segment.trim_start().starts_with('@') {
+          output.push_str(segment);
+        } else {
+          output.push_str(&self.prefix_selector(segment, prefix));
+        }
+      } else {
+        // 大括号内的声明
+        match self.prefix_ids.then(|| self.prefix_urls(segment, prefix)).flatten() {
+          Some(replaced) => output.push_str(&replaced),
+          None => output.push_str(segment),
+        }
+      }
+      output.push(c);
+      segment_start = index + 1;
+    }
+    output.push_str(&css[segment_start..]);
+    output
+  }
+
+  fn prefix_selector(&self, selector: &str, prefix: &str) -> String {
+    let mut output = String::with_capacity(selector.len() + prefix.len());
+    let mut in_brackets = false;
+    let mut quote: Option<char> = None;
+    let mut chars = selector.chars().peekable();
+    while let Some(c) = chars.next() {
+      output.push(c);
+      match (c, quote) {
+        (_, Some(q)) if c == q => quote = None,
+        (_, Some(_)) => {}
+        ('"' | '\'', None) => quote = Some(c),
+        ('[', None) => in_brackets = true,
+        (']', None) => in_brackets = false,
+        ('#', None) | ('.', None) if !in_brackets => {
+          let enabled = if c == '#' {
+            self.prefix_ids
+          } else {
+            self.prefix_class_names
+          };
+          if enabled && chars.peek().is_some_and(|n| is_ident_start(*n)) {
+            output.push_str(prefix);
+          }
+        }
+        _ => {}
+      }
+    }
+    output
+  }
+}
+
+impl<'a> Plugin<'a> for PrefixIdsPlugin<'a> {
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    let prefix = self.generate_prefix(el);
+    if prefix.is_empty() {
+      return VisitAction::Keep;
+    }
+
+    if el.name == "style" {
+      for child in el.children.iter_mut() {
+        match child {
+          XMLAstChild::Text(text) => {
+            text.value = self.arena.alloc_str(&self.prefix_css(text.value, &prefix));
+          }
+          XMLAstChild::Cdata(cdata) => {
+            cdata.value = self.arena.alloc_str(&self.prefix_css(cdata.value, &prefix));
+          }
+          _ => {}
+        }
+      }
+    }
+
+    for (name, value) in el.attributes.iter_mut() {
+      let prefixed = match *name {
+        "id" if self.prefix_ids => Some(prefix_id(&prefix, value).into_owned()),
+        "class" if self.prefix_class_names => Some(
+          value
+            .split_whitespace()
+            .map(|class| prefix_id(&prefix, class))
+            .collect::<Vec<_>>()
+            .join(" "),
+        ),
+        "href" | "xlink:href" if self.prefix_ids => {
+          value.strip_prefix('#').map(|id| format!("#{}", prefix_id(&prefix, id)))
+        }
+        "begin" | "end" if self.prefix_ids => Some(prefix_animation_refs(value, &prefix)),
+        _ if self.prefix_ids => self.prefix_urls(value, &prefix),
+        _ => None,
+      };
+      if let Some(prefixed) = prefixed {
+        *value = self.arena.alloc_str(&prefixed);
+      }
+    }
+    VisitAction::Keep
+  }
+}
+
+/// 已经带有前缀的名字不重复添加
+fn prefix_id<'s>(prefix: &str, value: &'s str) -> std::borrow::Cow<'s, str> {
+  if value.starts_with(prefix) {
+    std::borrow::Cow::Borrowed(value)
+  } else {
+    std::borrow::Cow::Owned(format!("{}{}", prefix, value))
+  }
+}
+
+/// `begin="elem.end; 1s"` 形式的动画时间引用
+fn prefix_animation_refs(value: &str, prefix: &str) -> String {
+  value
+    .split(';')
+    .map(|part| {
+      let trimmed = part.trim_start();
+      match trimmed.split_once('.') {
+        Some((id, _))
+          if !id.is_empty()
+            && trimmed.starts_with(|c: char| is_ident_start(c))
+            && id.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') =>
+        {
+          format!(
+            "{}{}",
+            &part[..part.len() - trimmed.len()],
+            prefix_id(prefix, trimmed)
+          )
+        }
+        _ => part.to_string(),
+      }
+    })
+    .collect::<Vec<_>>()
+    .join(";")
+}
+
+fn is_ident_start(c: char) -> bool {
+  c.is_alphabetic() || c == '_' || c == '-' || c == '\\' || !c.is_ascii()
+}
+
+fn get_basename(path: &str) -> Option<&str> {
+  path.rsplit(['/', '\\']).next().filter(|part| !part.is_empty())
+}
+
+fn escape_identifier_name(name: &str) -> String {
+  name.replace(['.', ' '], "_")
+}
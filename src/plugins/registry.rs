@@ -0,0 +1,841 @@
+//! 插件注册表：按 svgo 的插件名创建插件，并提供 `preset-default`
+use std::cell::RefCell;
+
+use bumpalo::Bump;
+use phf::{phf_set, Set};
+use regex::Regex;
+use serde_json::{json, Map, Value};
+
+use crate::optimizer::{NamedPlugin, Plugin};
+use crate::plugins::add_attributes_to_svg_element::{
+  AddAttributesToSVGElementPlugin, AddAttributesToSVGElementPluginConfig,
+};
+use crate::plugins::add_classes_to_svg_element::{
+  AddClassesToSVGElementPlugin, AddClassesToSVGElementPluginConfig,
+};
+use crate::plugins::cleanup_attrs::{CleanupAttrsPlugin, CleanupAttrsPluginConfig};
+use crate::plugins::cleanup_enable_background::{
+  CleanupEnableBackgroundPlugin, CleanupEnableBackgroundPluginConfig,
+};
+use crate::plugins::convert_one_stop_gradients::{
+  ConvertOneStopGradientsPlugin, ConvertOneStopGradientsPluginConfig,
+};
+use crate::plugins::convert_path_data::{ConvertPathDataPlugin, ConvertPathDataPluginConfig};
+use crate::plugins::merge_gradients::{MergeGradientsPlugin, MergeGradientsPluginConfig};
+use crate::plugins::move_elems_attrs_to_group::{
+  MoveElemsAttrsToGroupPlugin, MoveElemsAttrsToGroupPluginConfig,
+};
+use crate::plugins::move_group_attrs_to_elems::{
+  MoveGroupAttrsToElemsPlugin, MoveGroupAttrsToElemsPluginConfig,
+};
+use crate::plugins::prefix_ids::{PrefixIdsPlugin, PrefixIdsPluginConfig, PrefixIdsPrefix};
+use crate::plugins::remove_attrs::{
+  RemoveAttrsPattern, RemoveAttrsPlugin, RemoveAttrsPluginConfig,
+};
+use crate::plugins::remove_comments::{RemoveCommentsConfig, RemoveCommentsPlugin};
+use crate::plugins::remove_desc::{RemoveDescPlugin, RemoveDescPluginConfig};
+use crate::plugins::remove_dimensions::{RemoveDimensionsPlugin, RemoveDimensionsPluginConfig};
+use crate::plugins::remove_doctype::{RemoveDoctypePlugin, RemoveDoctypePluginConfig};
+use crate::plugins::remove_editors_ns_data::{RemoveEditorsNSData, RemoveEditorsNSDataConfig};
+use crate::plugins::remove_elements_by_attr::{
+  RemoveElementsByAttrPlugin, RemoveElementsByAttrPluginConfig,
+};
+use crate::plugins::remove_empty_attrs::{RemoveEmptyAttrsPlugin, RemoveEmptyAttrsPluginConfig};
+use crate::plugins::remove_empty_text::{RemoveEmptyTextPlugin, RemoveEmptyTextPluginConfig};
+use crate::plugins::remove_metadata::{RemoveMetadataPlugin, RemoveMetadataPluginConfig};
+use crate::plugins::remove_non_inheritable_group_attrs::{
+  RemoveNonInheritableGroupAttrsPlugin, RemoveNonInheritableGroupAttrsPluginConfig,
+};
+use crate::plugins::remove_off_canvas_paths::{
+  RemoveOffCanvasPathsPlugin, RemoveOffCanvasPathsPluginConfig,
+};
+use crate::plugins::remove_raster_images::{
+  RemoveRasterImagesPlugin, RemoveRasterImagesPluginConfig,
+};
+use crate::plugins::remove_scripts::{RemoveScriptsPlugin, RemoveScriptsPluginConfig};
+use crate::plugins::remove_style_element::{
+  RemoveStyleElementPlugin, RemoveStyleElementPluginConfig,
+};
+use crate::plugins::remove_title::{RemoveTitlePlugin, RemoveTitlePluginConfig};
+use crate::plugins::remove_unused_ns::{RemoveUnusedNSPlugin, RemoveUnusedNSPluginConfig};
+use crate::plugins::remove_useless_stroke_and_fill::{
+  RemoveUselessStrokeAndFillPlugin, RemoveUselessStrokeAndFillPluginConfig,
+};
+use crate::plugins::remove_view_box::{RemoveViewBoxPlugin, RemoveViewBoxPluginConfig};
+use crate::plugins::remove_xlink::{RemoveXlinkPlugin, RemoveXlinkPluginConfig};
+use crate::plugins::remove_xml_proc_inst::{
+  RemoveXMLProcInstPlugin, RemoveXMLProcInstPluginConfig,
+};
+use crate::plugins::remove_xmlns::{RemoveXMLNSPlugin, RemoveXMLNSPluginConfig};
+use crate::plugins::reuse_paths::{ReusePathsPlugin, ReusePathsPluginConfig};
+use crate::plugins::sort_attrs::{SortAttrsPlugin, SortAttrsPluginConfig, XmlnsOrder};
+use crate::plugins::sort_defs_children::{SortDefsChildrenPlugin, SortDefsChildrenPluginConfig};
+
+pub const PRESET_DEFAULT: &str = "preset-default";
+
+/// 插件列表中的一项，对应 svgo 配置里的 `'name'` 或 `{ name, params }`
+#[derive(Debug, Clone)]
+pub struct PluginConfig {
+  pub name: String,
+  pub params: Option<Value>,
+}
+
+impl PluginConfig {
+  pub fn new(name: &str, params: Option<Value>) -> Self {
+    PluginConfig {
+      name: name.to_string(),
+      params,
+    }
+  }
+}
+
+/// 创建插件时除参数外需要的信息
+pub struct PluginContext<'a> {
+  pub arena: &'a Bump,
+  /// 被优化文件的路径，`prefixIds` 用它生成默认前缀
+  pub path: Option<String>,
+  /// 与 svgo 的全局 `floatPrecision` 相同，优先于插件参数中的 `floatPrecision`
+  pub float_precision: Option<u32>,
+}
+
+pub struct PluginInfo {
+  pub name: &'static str,
+  pub description: &'static str,
+}
+
+/// 所有内置插件，按名字排序
+pub static BUILTIN_PLUGINS: &[PluginInfo] = &[
+  PluginInfo {
+    name: "addAttributesToSVGElement",
+    description: "adds attributes to an outer <svg> element",
+  },
+  PluginInfo {
+    name: "addClassesToSVGElement",
+    description: "adds classnames to an outer <svg> element",
+  },
+  PluginInfo {
+    name: "cleanupAttrs",
+    description: "cleanups attributes from newlines, trailing and repeating spaces",
+  },
+  PluginInfo {
+    name: "cleanupEnableBackground",
+    description: "remove or cleanup enable-background attribute when possible",
+  },
+  PluginInfo {
+    name: "convertOneStopGradients",
+    description: "converts one-stop (single color) gradients to a plain color",
+  },
+  PluginInfo {
+    name: "convertPathData",
+    description: "optimizes path data: applies transforms to path coordinates",
+  },
+  PluginInfo {
+    name: "mergeGradients",
+    description: "merges gradients with identical attributes and stops",
+  },
+  PluginInfo {
+    name: "moveElemsAttrsToGroup",
+    description: "Move common attributes of group children to the group",
+  },
+  PluginInfo {
+    name: "moveGroupAttrsToElems",
+    description: "moves some group attributes to the content elements",
+  },
+  PluginInfo {
+    name: "prefixIds",
+    description: "prefix IDs",
+  },
+  PluginInfo {
+    name: "removeAttrs",
+    description: "removes specified attributes",
+  },
+  PluginInfo {
+    name: "removeComments",
+    description: "removes comments",
+  },
+  PluginInfo {
+    name: "removeDesc",
+    description: "removes <desc>",
+  },
+  PluginInfo {
+    name: "removeDimensions",
+    description: "removes width and height in presence of viewBox (opposite to removeViewBox)",
+  },
+  PluginInfo {
+    name: "removeDoctype",
+    description: "removes doctype declaration",
+  },
+  PluginInfo {
+    name: "removeEditorsNSData",
+    description: "removes editors namespaces, elements and attributes",
+  },
+  PluginInfo {
+    name: "removeElementsByAttr",
+    description: "removes arbitrary elements by ID or className",
+  },
+  PluginInfo {
+    name: "removeEmptyAttrs",
+    description: "removes empty attributes",
+  },
+  PluginInfo {
+    name: "removeEmptyText",
+    description: "removes empty <text> elements",
+  },
+  PluginInfo {
+    name: "removeMetadata",
+    description: "removes <metadata>",
+  },
+  PluginInfo {
+    name: "removeNonInheritableGroupAttrs",
+    description: "removes non-inheritable group's presentational attributes",
+  },
+  PluginInfo {
+    name: "removeOffCanvasPaths",
+    description: "removes elements that are drawn outside of the viewBox",
+  },
+  PluginInfo {
+    name: "removeRasterImages",
+    description: "removes raster images",
+  },
+  PluginInfo {
+    name: "removeScripts",
+    description: "removes scripts",
+  },
+  PluginInfo {
+    name: "removeStyleElement",
+    description: "removes <style> element",
+  },
+  PluginInfo {
+    name: "removeTitle",
+    description: "removes <title>",
+  },
+  PluginInfo {
+    name: "removeUnusedNS",
+    description: "removes unused namespaces declaration",
+  },
+  PluginInfo {
+    name: "removeUselessStrokeAndFill",
+    description: "removes useless stroke and fill attributes",
+  },
+  PluginInfo {
+    name: "removeViewBox",
+    description: "removes viewBox attribute when possible",
+  },
+  PluginInfo {
+    name: "removeXlink",
+    description: "remove xlink namespace and replaces attributes with the SVG 2 equivalent",
+  },
+  PluginInfo {
+    name: "removeXMLNS",
+    description: "removes xmlns attribute (for inline svg)",
+  },
+  PluginInfo {
+    name: "removeXMLProcInst",
+    description: "removes XML processing instructions",
+  },
+  PluginInfo {
+    name: "reusePaths",
+    description: "Finds <path> elements with the same d, fill, and stroke, and converts them to <use> elements referencing a single <path> def.",
+  },
+  PluginInfo {
+    name: "sortAttrs",
+    description: "Sort element attributes for better compression",
+  },
+  PluginInfo {
+    name: "sortDefsChildren",
+    description: "Sorts children of <defs> to improve compression",
+  },
+];
+
+/// svgo `preset-default` 中已经实现的插件，顺序与 svgo 一致
+pub const PRESET_DEFAULT_PLUGINS: [&str; 20] = [
+  "removeDoctype",
+  "removeXMLProcInst",
+  "removeComments",
+  "removeMetadata",
+  "removeEditorsNSData",
+  "cleanupAttrs",
+  "removeNonInheritableGroupAttrs",
+  "removeUselessStrokeAndFill",
+  "removeViewBox",
+  "cleanupEnableBackground",
+  "removeEmptyText",
+  "moveElemsAttrsToGroup",
+  "moveGroupAttrsToElems",
+  "convertPathData",
+  "removeEmptyAttrs",
+  "removeUnusedNS",
+  "sortAttrs",
+  "sortDefsChildren",
+  "removeTitle",
+  "removeDesc",
+];
+
+/// svgo 中存在但尚未实现的插件，配置中出现时跳过而不是报错，方便直接迁移 svgo 的配置。
+/// 被跳过的插件由 [`skipped_plugins`] 报告给调用方
+static UNIMPLEMENTED_PLUGINS: Set<&'static str> = phf_set! {
+  "cleanupIds",
+  "cleanupListOfValues",
+  "cleanupNumericValues",
+  "collapseGroups",
+  "convertColors",
+  "convertEllipseToCircle",
+  "convertShapeToPath",
+  "convertStyleToAttrs",
+  "convertTransform",
+  "inlineStyles",
+  "mergePaths",
+  "mergeStyles",
+  "minifyStyles",
+  "removeAttributesBySelector",
+  "removeDeprecatedAttrs",
+  "removeEmptyContainers",
+  "removeHiddenElems",
+  "removeUnknownsAndDefaults",
+  "removeUselessDefs",
+};
+
+/// 没有指定插件列表时使用的插件
+pub fn default_plugins() -> Vec<PluginConfig> {
+  vec![
+    PluginConfig::new("removeDesc", Some(json!({ "removeAny": true }))),
+    PluginConfig::new("removeDoctype", None),
+    PluginConfig::new("removeTitle", None),
+    PluginConfig::new("removeComments", None),
+    PluginConfig::new("removeXMLProcInst", None),
+    PluginConfig::new("removeMetadata", None),
+    PluginConfig::new("moveElemsAttrsToGroup", None),
+    PluginConfig::new("removeEditorsNSData", None),
+  ]
+}
+
+/// 把插件列表展开为插件实例，`preset-default` 会被替换为其中的插件
+pub fn create_plugins<'a>(
+  configs: &[PluginConfig],
+  context: &PluginContext<'a>,
+) -> Result<Vec<NamedPlugin<'a>>, String> {
+  let mut plugins = Vec::new();
+  for config in configs {
+    if config.name == PRESET_DEFAULT {
+      for config in expand_preset_default(config.params.as_ref())? {
+        plugins.push(NamedPlugin::new(
+          &config.name,
+          create_plugin(&config, context)?,
+        ));
+      }
+    } else if UNIMPLEMENTED_PLUGINS.contains(config.name.as_str()) {
+      log::warn!("plugin \"{}\" is not implemented yet, skipped", config.name);
+    } else {
+      plugins.push(NamedPlugin::new(
+        &config.name,
+        create_plugin(config, context)?,
+      ));
+    }
+  }
+  Ok(plugins)
+}
+
+/// 配置中请求了、但因为尚未实现而被跳过的插件，包括 `preset-default` 的 `overrides`
+/// 中没有设为 `false` 的插件，按出现顺序去重
+pub fn skipped_plugins(configs: &[PluginConfig]) -> Vec<String> {
+  let mut skipped: Vec<String> = Vec::new();
+  for config in configs {
+    let names: Vec<&str> = if config.name == PRESET_DEFAULT {
+      let overrides = config.params.as_ref().and_then(|params| params.get("overrides"));
+      match overrides {
+        Some(Value::Object(overrides)) => overrides
+          .iter()
+          .filter(|(_, value)| **value != Value::Bool(false))
+          .map(|(name, _)| name.as_str())
+          .collect(),
+        _ => Vec::new(),
+      }
+    } else {
+      vec![config.name.as_str()]
+    };
+    for name in names {
+      if UNIMPLEMENTED_PLUGINS.contains(name) && !skipped.iter().any(|s| s == name) {
+        skipped.push(name.to_string());
+      }
+    }
+  }
+  skipped
+}
+
+/// 按 `overrides` 展开 `preset-default`：`false` 禁用插件，对象作为插件参数
+pub fn expand_preset_default(params: Option<&Value>) -> Result<Vec<PluginConfig>, String> {
+  let params = Params::new(PRESET_DEFAULT, params)?;
+  let overrides = match params.get("overrides") {
+    None => Map::new(),
+    Some(Value::Object(overrides)) => overrides.clone(),
+    Some(_) => return Err(params.error("overrides", "an object")),
+  };
+  // 与 svgo 相同，作为用到它的插件的默认参数
+  let float_precision = params.u32("floatPrecision")?;
+  params.finish()?;
+  for name in overrides.keys() {
+    let known = PRESET_DEFAULT_PLUGINS.contains(&name.as_str())
+      || UNIMPLEMENTED_PLUGINS.contains(name.as_str());
+    if !known {
+      return Err(format!(
+        "{}: \"{}\" is not a plugin of the preset{}",
+        PRESET_DEFAULT,
+        name,
+        did_you_mean(name, PRESET_DEFAULT_PLUGINS.iter().copied())
+      ));
+    }
+  }
+
+  let mut configs = Vec::new();
+  for name in PRESET_DEFAULT_PLUGINS {
+    let mut params = match overrides.get(name) {
+      Some(Value::Bool(false)) => continue,
+      Some(Value::Bool(true)) | None => Map::new(),
+      Some(Value::Object(params)) => params.clone(),
+      Some(_) => {
+        return Err(format!(
+          "{}: override for \"{}\" must be false or an object",
+          PRESET_DEFAULT, name
+        ))
+      }
+    };
+    if let (Some(float_precision), "convertPathData") = (float_precision, name) {
+      params.entry("floatPrecision").or_insert(json!(float_precision));
+    }
+    let params = (!params.is_empty()).then_some(Value::Object(params));
+    configs.push(PluginConfig::new(name, params));
+  }
+  Ok(configs)
+}
+
+/// 根据名字和参数创建单个插件
+pub fn create_plugin<'a>(
+  config: &PluginConfig,
+  context: &PluginContext<'a>,
+) -> Result<Box<dyn Plugin<'a> + 'a>, String> {
+  let name = config.name.as_str();
+  let params = Params::new(name, config.params.as_ref())?;
+  let arena = context.arena;
+  let plugin: Box<dyn Plugin<'a> + 'a> = match name {
+    "addAttributesToSVGElement" => {
+      let mut attributes = Vec::new();
+      let single = params.get("attribute").into_iter();
+      let many = match params.get("attributes") {
+        None => vec![],
+        Some(Value::Array(items)) => items.iter().collect(),
+        Some(_) => return Err(params.error("attributes", "an array")),
+      };
+      for attribute in single.chain(many) {
+        match attribute {
+          Value::String(name) => attributes.push((name.clone(), None)),
+          Value::Object(map) => {
+            for (key, value) in map {
+              let value = value
+                .as_str()
+                .ok_or_else(|| params.error("attributes", "strings as attribute values"))?;
+              attributes.push((key.clone(), Some(value.to_string())));
+            }
+          }
+          _ => return Err(params.error("attributes", "strings or objects")),
+        }
+      }
+      if attributes.is_empty() {
+        return Err(format!(
+          "{}: either `attribute` or `attributes` is required",
+          name
+        ));
+      }
+      Box::new(AddAttributesToSVGElementPlugin::new(
+        AddAttributesToSVGElementPluginConfig { attributes },
+        arena,
+      ))
+    }
+    "addClassesToSVGElement" => {
+      let mut class_names = params.string("className")?.into_iter().collect::<Vec<_>>();
+      class_names.extend(params.strings("classNames")?.unwrap_or_default());
+      if class_names.is_empty() {
+        return Err(format!(
+          "{}: either `className` or `classNames` is required",
+          name
+        ));
+      }
+      Box::new(AddClassesToSVGElementPlugin::new(
+        AddClassesToSVGElementPluginConfig { class_names },
+        arena,
+      ))
+    }
+    "cleanupAttrs" => Box::new(CleanupAttrsPlugin::new(
+      CleanupAttrsPluginConfig {
+        newlines: params.bool("newlines")?,
+        trim: params.bool("trim")?,
+        spaces: params.bool("spaces")?,
+      },
+      arena,
+    )),
+    "cleanupEnableBackground" => Box::new(CleanupEnableBackgroundPlugin::new(
+      CleanupEnableBackgroundPluginConfig {},
+      arena,
+    )),
+    "convertOneStopGradients" => Box::new(ConvertOneStopGradientsPlugin::new(
+      ConvertOneStopGradientsPluginConfig {},
+      arena,
+    )),
+    "convertPathData" => {
+      params.ignore(CONVERT_PATH_DATA_SVGO_PARAMS);
+      Box::new(ConvertPathDataPlugin::new(
+        ConvertPathDataPluginConfig {
+          apply_transforms: params.bool("applyTransforms")?,
+          apply_transforms_stroked: params.bool("applyTransformsStroked")?,
+          // 与 svgo 相同，全局的 floatPrecision 优先于插件参数
+          float_precision: context.float_precision.or(params.u32("floatPrecision")?),
+        },
+        arena,
+      ))
+    }
+    "mergeGradients" => Box::new(MergeGradientsPlugin::new(
+      MergeGradientsPluginConfig {},
+      arena,
+    )),
+    "moveElemsAttrsToGroup" => Box::new(MoveElemsAttrsToGroupPlugin::new(
+      MoveElemsAttrsToGroupPluginConfig {},
+      arena,
+    )),
+    "moveGroupAttrsToElems" => Box::new(MoveGroupAttrsToElemsPlugin::new(
+      MoveGroupAttrsToElemsPluginConfig {},
+      arena,
+    )),
+    "prefixIds" => {
+      let prefix = match params.get("prefix") {
+        None | Some(Value::Bool(true)) => PrefixIdsPrefix::Default,
+        Some(Value::Bool(false)) => PrefixIdsPrefix::Disabled,
+        Some(Value::String(prefix)) => PrefixIdsPrefix::Static(prefix.clone()),
+        Some(_) => return Err(params.error("prefix", "a boolean or a string")),
+      };
+      Box::new(PrefixIdsPlugin::new(
+        PrefixIdsPluginConfig {
+          delim: params.string("delim")?,
+          prefix,
+          prefix_ids: params.bool("prefixIds")?,
+          prefix_class_names: params.bool("prefixClassNames")?,
+          path: context.path.clone(),
+        },
+        arena,
+      ))
+    }
+    "removeAttrs" => {
+      let attrs = params
+        .strings("attrs")?
+        .ok_or_else(|| format!("{}: `attrs` is required", name))?;
+      let elem_separator = params.string("elemSeparator")?;
+      let patterns = attrs
+        .iter()
+        .map(|pattern| RemoveAttrsPattern::parse(pattern, elem_separator.as_deref().unwrap_or(":")))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("{}: {}", name, e))?;
+      Box::new(RemoveAttrsPlugin::new(
+        RemoveAttrsPluginConfig {
+          patterns,
+          preserve_current_color: params.bool("preserveCurrentColor")?,
+        },
+        arena,
+      ))
+    }
+    "removeComments" => {
+      let preserve_patterns = match params.get("preservePatterns") {
+        None => None,
+        Some(Value::Bool(false)) => Some(vec![]),
+        Some(_) => Some(
+          params
+            .strings("preservePatterns")?
+            .unwrap_or_default()
+            .iter()
+            .map(|pattern| Regex::new(pattern))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("{}: {}", name, e))?,
+        ),
+      };
+      Box::new(RemoveCommentsPlugin::new(
+        RemoveCommentsConfig { preserve_patterns },
+        arena,
+      ))
+    }
+    "removeDesc" => Box::new(RemoveDescPlugin::new(
+      RemoveDescPluginConfig {
+        remove_any: params.bool("removeAny")?.unwrap_or(false),
+      },
+      arena,
+    )),
+    "removeDimensions" => Box::new(RemoveDimensionsPlugin::new(
+      RemoveDimensionsPluginConfig {},
+      arena,
+    )),
+    "removeDoctype" => Box::new(RemoveDoctypePlugin::new(
+      RemoveDoctypePluginConfig {},
+      arena,
+    )),
+    "removeEditorsNSData" => {
+      let additional_namespace = params.strings("additionalNamespaces")?.map(|namespaces| {
+        namespaces.iter().map(|namespace| arena.alloc_str(namespace) as &str).collect()
+      });
+      Box::new(RemoveEditorsNSData::new(
+        RemoveEditorsNSDataConfig {
+          additional_namespace,
+        },
+        arena,
+      ))
+    }
+    "removeElementsByAttr" => Box::new(RemoveElementsByAttrPlugin::new(
+      RemoveElementsByAttrPluginConfig {
+        id: params.strings("id")?.unwrap_or_default(),
+        class: params.strings("class")?.unwrap_or_default(),
+      },
+      arena,
+    )),
+    "removeEmptyAttrs" => Box::new(RemoveEmptyAttrsPlugin::new(
+      RemoveEmptyAttrsPluginConfig {},
+      arena,
+    )),
+    "removeEmptyText" => Box::new(RemoveEmptyTextPlugin::new(
+      RemoveEmptyTextPluginConfig {
+        text: params.bool("text")?,
+        tspan: params.bool("tspan")?,
+        tref: params.bool("tref")?,
+      },
+      arena,
+    )),
+    "removeMetadata" => Box::new(RemoveMetadataPlugin::new(
+      RemoveMetadataPluginConfig {},
+      arena,
+    )),
+    "removeNonInheritableGroupAttrs" => Box::new(RemoveNonInheritableGroupAttrsPlugin::new(
+      RemoveNonInheritableGroupAttrsPluginConfig {},
+      arena,
+    )),
+    "removeOffCanvasPaths" => Box::new(RemoveOffCanvasPathsPlugin::new(
+      RemoveOffCanvasPathsPluginConfig {},
+      arena,
+    )),
+    "removeRasterImages" => Box::new(RemoveRasterImagesPlugin::new(
+      RemoveRasterImagesPluginConfig {},
+      arena,
+    )),
+    "removeScripts" => Box::new(RemoveScriptsPlugin::new(
+      RemoveScriptsPluginConfig {},
+      arena,
+    )),
+    "removeStyleElement" => Box::new(RemoveStyleElementPlugin::new(
+      RemoveStyleElementPluginConfig {},
+      arena,
+    )),
+    "removeTitle" => Box::new(RemoveTitlePlugin::new(RemoveTitlePluginConfig {}, arena)),
+    "removeUnusedNS" => Box::new(RemoveUnusedNSPlugin::new(
+      RemoveUnusedNSPluginConfig {},
+      arena,
+    )),
+    "removeUselessStrokeAndFill" => Box::new(RemoveUselessStrokeAndFillPlugin::new(
+      RemoveUselessStrokeAndFillPluginConfig {
+        stroke: params.bool("stroke")?,
+        fill: params.bool("fill")?,
+        remove_none: params.bool("removeNone")?,
+      },
+      arena,
+    )),
+    "removeViewBox" => Box::new(RemoveViewBoxPlugin::new(
+      RemoveViewBoxPluginConfig {},
+      arena,
+    )),
+    "removeXlink" => Box::new(RemoveXlinkPlugin::new(
+      RemoveXlinkPluginConfig {
+        include_legacy: params.bool("includeLegacy")?,
+      },
+      arena,
+    )),
+    "removeXMLNS" => Box::new(RemoveXMLNSPlugin::new(RemoveXMLNSPluginConfig {}, arena)),
+    "removeXMLProcInst" => Box::new(RemoveXMLProcInstPlugin::new(
+      RemoveXMLProcInstPluginConfig {},
+      arena,
+    )),
+    "reusePaths" => Box::new(ReusePathsPlugin::new(ReusePathsPluginConfig {}, arena)),
+    "sortAttrs" => {
+      let xmlns_order = match params.string("xmlnsOrder")?.as_deref() {
+        None | Some("front") => None,
+        Some("alphabetical") => Some(XmlnsOrder::Alphabetical),
+        Some(other) => {
+          return Err(format!(
+            "{}: unknown xmlnsOrder \"{}\", expected \"front\" or \"alphabetical\"",
+            name, other
+          ))
+        }
+      };
+      Box::new(SortAttrsPlugin::new(
+        SortAttrsPluginConfig {
+          order: params.strings("order")?,
+          xmlns_order,
+        },
+        arena,
+      ))
+    }
+    "sortDefsChildren" => Box::new(SortDefsChildrenPlugin::new(
+      SortDefsChildrenPluginConfig {},
+      arena,
+    )),
+    _ if UNIMPLEMENTED_PLUGINS.contains(name) => {
+      return Err(format!("plugin \"{}\" is not implemented yet", name))
+    }
+    _ => {
+      return Err(format!(
+        "unknown plugin \"{}\"{}",
+        name,
+        did_you_mean(name, BUILTIN_PLUGINS.iter().map(|plugin| plugin.name))
+      ))
+    }
+  };
+  params.finish()?;
+  Ok(plugin)
+}
+
+/// 名字拼错时给出最接近的候选，没有足够接近的候选时返回空字符串
+pub(crate) fn did_you_mean<'n>(name: &str, candidates: impl Iterator<Item = &'n str>) -> String {
+  let name = name.to_ascii_lowercase();
+  let threshold = (name.len() / 3).max(2);
+  candidates
+    .map(|candidate| {
+      (
+        edit_distance(&name, &candidate.to_ascii_lowercase()),
+        candidate,
+      )
+    })
+    .filter(|(distance, _)| *distance <= threshold)
+    .min_by_key(|(distance, _)| *distance)
+    .map(|(_, candidate)| format!(", did you mean \"{}\"?", candidate))
+    .unwrap_or_default()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+  let b: Vec<char> = b.chars().collect();
+  let mut row: Vec<usize> = (0..=b.len()).collect();
+  for (i, ca) in a.chars().enumerate() {
+    let mut diagonal = row[0];
+    row[0] = i + 1;
+    for (j, cb) in b.iter().enumerate() {
+      let substitution = diagonal + usize::from(ca != *cb);
+      diagonal = row[j + 1];
+      row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+    }
+  }
+  row[b.len()]
+}
+
+/// svgo 中 `convertPathData` 尚未实现的优化对应的参数，可以出现在配置中但不起作用
+const CONVERT_PATH_DATA_SVGO_PARAMS: &[&str] = &[
+  "collapseRepeated",
+  "convertToQ",
+  "convertToZ",
+  "curveSmoothShorthands",
+  "forceAbsolutePath",
+  "leadingZero",
+  "lineShorthands",
+  "makeArcs",
+  "negativeExtraSpace",
+  "noSpaceAfterFlags",
+  "removeUseless",
+  "smartArcRounding",
+  "straightCurves",
+  "transformPrecision",
+  "utilizeAbsolute",
+];
+
+/// 插件参数的读取工具，参数类型不对时返回带插件名的错误
+struct Params<'p> {
+  plugin: &'p str,
+  map: Option<&'p Map<String, Value>>,
+  /// 读取过的参数名，其余的参数都是未知参数
+  known: RefCell<Vec<String>>,
+}
+
+impl<'p> Params<'p> {
+  fn new(plugin: &'p str, params: Option<&'p Value>) -> Result<Self, String> {
+    let map = match params {
+      None | Some(Value::Null) => None,
+      Some(Value::Object(map)) => Some(map),
+      Some(_) => return Err(format!("{}: params must be an object", plugin)),
+    };
+    Ok(Params {
+      plugin,
+      map,
+      known: RefCell::new(Vec::new()),
+    })
+  }
+
+  /// 不存在或为 `null` 时返回 `None`
+  fn get(&self, key: &str) -> Option<&'p Value> {
+    self.known.borrow_mut().push(key.to_string());
+    self.map.and_then(|map| map.get(key)).filter(|value| !value.is_null())
+  }
+
+  /// 接受但不使用的参数
+  fn ignore(&self, keys: &[&str]) {
+    self.known.borrow_mut().extend(keys.iter().map(|key| key.to_string()));
+  }
+
+  /// 参数中有没读取过的参数时报错，拼错时提示最接近的参数名
+  fn finish(&self) -> Result<(), String> {
+    let known = self.known.borrow();
+    let unknown = self.map.and_then(|map| map.keys().find(|key| !known.contains(key)));
+    match unknown {
+      None => Ok(()),
+      Some(key) => Err(format!(
+        "{}: unknown param `{}`{}",
+        self.plugin,
+        key,
+        did_you_mean(key, known.iter().map(String::as_str))
+      )),
+    }
+  }
+
+  fn error(&self, key: &str, expected: &str) -> String {
+    format!("{}: `{}` must be {}", self.plugin, key, expected)
+  }
+
+  fn bool(&self, key: &str) -> Result<Option<bool>, String> {
+    match self.get(key) {
+      None => Ok(None),
+      Some(Value::Bool(value)) => Ok(Some(*value)),
+      Some(_) => Err(self.error(key, "a boolean")),
+    }
+  }
+
+  fn u32(&self, key: &str) -> Result<Option<u32>, String> {
+    match self.get(key) {
+      None => Ok(None),
+      Some(value) => value
+        .as_u64()
+        .and_then(|value| u32::try_from(value).ok())
+        .map(Some)
+        .ok_or_else(|| self.error(key, "a non-negative integer")),
+    }
+  }
+
+  fn string(&self, key: &str) -> Result<Option<String>, String> {
+    match self.get(key) {
+      None => Ok(None),
+      Some(Value::String(value)) => Ok(Some(value.clone())),
+      Some(_) => Err(self.error(key, "a string")),
+    }
+  }
+
+  /// svgo 的参数常常既可以是单个字符串也可以是数组
+  fn strings(&self, key: &str) -> Result<Option<Vec<String>>, String> {
+    match self.get(key) {
+      None => Ok(None),
+      Some(Value::String(value)) => Ok(Some(vec![value.clone()])),
+      Some(Value::Array(items)) => items
+        .iter()
+        .map(|item| item.as_str().map(str::to_string))
+        .collect::<Option<Vec<_>>>()
+        .map(Some)
+        .ok_or_else(|| self.error(key, "a string or an array of strings")),
+      Some(_) => Err(self.error(key, "a string or an array of strings")),
+    }
+  }
+}
@@ -0,0 +1,91 @@
+use bumpalo::Bump;
+use regex::{Error as RegexError, Regex};
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+
+/// Remove attributes matching `element:attribute:value` patterns.
+///
+/// 每段都是大小写不敏感的正则表达式，`*` 表示匹配任意内容：
+///
+/// - `fill` 删除所有元素上的 `fill`
+/// - `path:fill` 只删除 `<path>` 上的 `fill`
+/// - `path:fill:none` 只删除 `<path>` 上值为 `none` 的 `fill`
+#[allow(dead_code)]
+pub struct RemoveAttrsPlugin<'a> {
+  patterns: Vec<RemoveAttrsPattern>,
+  preserve_current_color: bool,
+  arena: &'a Bump,
+}
+
+/// 分别匹配元素名、属性名和属性值
+pub struct RemoveAttrsPattern {
+  element: Regex,
+  name: Regex,
+  value: Regex,
+}
+
+impl RemoveAttrsPattern {
+  /// 按 svgo 的规则解析 pattern，缺少的部分视为 `*`
+  pub fn parse(pattern: &str, elem_separator: &str) -> Result<Self, RegexError> {
+    let parts: Vec<&str> = pattern.split(elem_separator).collect();
+    let (element, name, value) = match parts.as_slice() {
+      [name] => (".*", *name, ".*"),
+      [element, name] => (*element, *name, ".*"),
+      [element, name, value, ..] => (*element, *name, *value),
+      [] => unreachable!(),
+    };
+    let build = |part: &str| {
+      let part = if part == "*" { ".*" } else { part };
+      Regex::new(&format!("(?i)^(?:{})$", part))
+    };
+    Ok(RemoveAttrsPattern {
+      element: build(element)?,
+      name: build(name)?,
+      value: build(value)?,
+    })
+  }
+}
+
+pub struct RemoveAttrsPluginConfig {
+  pub patterns: Vec<RemoveAttrsPattern>,
+  /// 保留值为 `currentColor` 的 `fill` 和 `stroke`，默认 `false`
+  pub preserve_current_color: Option<bool>,
+}
+
+impl<'a> RemoveAttrsPlugin<'a> {
+  pub fn new(config: RemoveAttrsPluginConfig, arena: &'a Bump) -> Self {
+    RemoveAttrsPlugin {
+      patterns: config.patterns,
+      preserve_current_color: config.preserve_current_color.unwrap_or(false),
+      arena,
+    }
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveAttrsPlugin<'a> {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    for pattern in &self.patterns {
+      if !pattern.element.is_match(el.name) {
+        continue;
+      }
+      el.attributes.retain(|(name, value)| {
+        let is_current_color =
+          (*name == "fill" || *name == "stroke") && value.eq_ignore_ascii_case("currentcolor");
+        if self.preserve_current_color && is_current_color {
+          return true;
+        }
+        !(pattern.name.is_match(name) && pattern.value.is_match(value))
+      });
+    }
+    VisitAction::Keep
+  }
+}
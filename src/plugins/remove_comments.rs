@@ -1,4 +1,4 @@
-use crate::optimizer::{Plugin, VisitAction};
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
 use bumpalo::Bump;
 use regex::Regex;
 
@@ -26,7 +26,15 @@ impl<'a> RemoveCommentsPlugin<'a> {
 }
 
 impl<'a> Plugin<'a> for RemoveCommentsPlugin<'a> {
-  fn comment_enter(&self, _el: &mut crate::parser::XMLAstComment<'a>) -> VisitAction {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn comment_enter(
+    &mut self,
+    _el: &mut crate::parser::XMLAstComment<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     // Iterate through the patterns to preserve
     for pattern in &self.preserve_patterns {
       // Check if the comment text matches the current pattern
@@ -1,6 +1,6 @@
 use bumpalo::Bump;
 
-use crate::optimizer::{Plugin, VisitAction};
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
 use crate::parser::{XMLAstChild, XMLAstElement};
 
 /// Removes `<desc>`.
@@ -27,7 +27,11 @@ impl<'a> RemoveDescPlugin<'a> {
 }
 
 impl<'a> Plugin<'a> for RemoveDescPlugin<'a> {
-  fn element_enter(&mut self, el: &mut XMLAstElement<'a>) -> VisitAction {
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     if el.name == "desc" {
       if self.remove_any {
         return VisitAction::Remove;
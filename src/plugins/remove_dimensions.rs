@@ -0,0 +1,64 @@
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+
+/// Remove `width`/`height` from the root `<svg>` and add `viewBox` if it's missing.
+///
+/// ```svg
+/// <svg width="100" height="50"/>
+///              ⬇
+/// <svg viewBox="0 0 100 50"/>
+/// ```
+///
+/// 只有 `px` 或无单位的尺寸才能换算成 `viewBox`，其余单位（`em`、`%` 等）保持不变。
+pub struct RemoveDimensionsPlugin<'a> {
+  arena: &'a Bump,
+}
+
+pub struct RemoveDimensionsPluginConfig {}
+
+impl<'a> RemoveDimensionsPlugin<'a> {
+  pub fn new(_config: RemoveDimensionsPluginConfig, arena: &'a Bump) -> Self {
+    RemoveDimensionsPlugin { arena }
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveDimensionsPlugin<'a> {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    if el.name != "svg" || ctx.parent().is_some() {
+      return VisitAction::Keep;
+    }
+
+    if el.has_attribute("viewBox") {
+      el.remove_attribute("width");
+      el.remove_attribute("height");
+      return VisitAction::Keep;
+    }
+
+    let width = el.get_attribute("width").and_then(parse_length);
+    let height = el.get_attribute("height").and_then(parse_length);
+    if let (Some(width), Some(height)) = (width, height) {
+      let view_box = bumpalo::format!(in self.arena, "0 0 {} {}", width, height).into_bump_str();
+      el.remove_attribute("width");
+      el.remove_attribute("height");
+      el.set_attribute("viewBox", view_box);
+    }
+    VisitAction::Keep
+  }
+}
+
+/// 解析 `px` 或无单位的长度，其它单位无法换算为用户坐标，返回 `None`
+pub(crate) fn parse_length(value: &str) -> Option<f64> {
+  let value = value.trim();
+  let number = value.strip_suffix("px").unwrap_or(value);
+  number.parse::<f64>().ok().filter(|n| n.is_finite())
+}
@@ -1,6 +1,6 @@
 use bumpalo::Bump;
 
-use crate::optimizer::{Plugin, VisitAction};
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
 use crate::parser::XMLAstDoctype;
 
 /// Remove DOCTYPE declaration.
@@ -18,7 +18,15 @@ impl<'a> RemoveDoctypePlugin<'a> {
 }
 
 impl<'a> Plugin<'a> for RemoveDoctypePlugin<'a> {
-  fn doctype_enter(&self, _el: &mut XMLAstDoctype<'a>) -> VisitAction {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn doctype_enter(
+    &mut self,
+    _el: &mut XMLAstDoctype<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     VisitAction::Remove
   }
 }
@@ -1,6 +1,6 @@
 use bumpalo::Bump;
 
-use crate::optimizer::{Plugin, VisitAction};
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
 
 /// Remove <title>.
 #[allow(dead_code)]
@@ -54,7 +54,11 @@ impl<'a> RemoveEditorsNSData<'a> {
 }
 
 impl<'a> Plugin<'a> for RemoveEditorsNSData<'a> {
-  fn element_enter(&mut self, el: &mut crate::parser::XMLAstElement<'a>) -> VisitAction {
+  fn element_enter(
+    &mut self,
+    el: &mut crate::parser::XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     // collect namespace prefixes from svg element
     let prefix = "xmlns:";
     let prefix_len = prefix.len();
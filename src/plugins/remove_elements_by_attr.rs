@@ -0,0 +1,61 @@
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+
+/// Remove arbitrary elements by `id` or `class` attribute.
+///
+/// ```svg
+/// <rect id="elementId"/>
+/// <g class="elementClass"><circle/></g>
+///              ⬇
+/// (both removed with `id: ["elementId"], class: ["elementClass"]`)
+/// ```
+#[allow(dead_code)]
+pub struct RemoveElementsByAttrPlugin<'a> {
+  ids: Vec<String>,
+  classes: Vec<String>,
+  arena: &'a Bump,
+}
+
+pub struct RemoveElementsByAttrPluginConfig {
+  pub id: Vec<String>,
+  pub class: Vec<String>,
+}
+
+impl<'a> RemoveElementsByAttrPlugin<'a> {
+  pub fn new(config: RemoveElementsByAttrPluginConfig, arena: &'a Bump) -> Self {
+    RemoveElementsByAttrPlugin {
+      ids: config.id,
+      classes: config.class,
+      arena,
+    }
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveElementsByAttrPlugin<'a> {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    if let Some(id) = el.get_attribute("id") {
+      if self.ids.iter().any(|remove_id| remove_id == id) {
+        return VisitAction::Remove;
+      }
+    }
+    if let Some(class) = el.get_attribute("class") {
+      if class
+        .split_whitespace()
+        .any(|name| self.classes.iter().any(|remove_class| remove_class == name))
+      {
+        return VisitAction::Remove;
+      }
+    }
+    VisitAction::Keep
+  }
+}
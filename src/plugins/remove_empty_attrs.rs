@@ -0,0 +1,45 @@
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+
+/// Remove attributes with empty values.
+///
+/// ```svg
+/// <path class="" d="M0 0"/>
+///              ⬇
+/// <path d="M0 0"/>
+/// ```
+///
+/// 条件处理属性（`requiredFeatures` 等）为空时有特殊含义，予以保留。
+#[allow(dead_code)]
+pub struct RemoveEmptyAttrsPlugin<'a> {
+  arena: &'a Bump,
+}
+
+pub struct RemoveEmptyAttrsPluginConfig {}
+
+const CONDITIONAL_PROCESSING_ATTRS: [&str; 3] =
+  ["requiredExtensions", "requiredFeatures", "systemLanguage"];
+
+impl<'a> RemoveEmptyAttrsPlugin<'a> {
+  pub fn new(_config: RemoveEmptyAttrsPluginConfig, arena: &'a Bump) -> Self {
+    RemoveEmptyAttrsPlugin { arena }
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveEmptyAttrsPlugin<'a> {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    el.attributes
+      .retain(|(name, value)| !value.is_empty() || CONDITIONAL_PROCESSING_ATTRS.contains(name));
+    VisitAction::Keep
+  }
+}
@@ -0,0 +1,59 @@
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+
+/// Remove empty text elements.
+///
+/// ```svg
+/// <g><text/><tspan/><tref/></g>
+///              ⬇
+/// <g/>
+/// ```
+#[allow(dead_code)]
+pub struct RemoveEmptyTextPlugin<'a> {
+  text: bool,
+  tspan: bool,
+  tref: bool,
+  arena: &'a Bump,
+}
+
+pub struct RemoveEmptyTextPluginConfig {
+  /// 删除没有子节点的 `<text>`，默认 `true`
+  pub text: Option<bool>,
+  /// 删除没有子节点的 `<tspan>`，默认 `true`
+  pub tspan: Option<bool>,
+  /// 删除没有 `xlink:href` 的 `<tref>`，默认 `true`
+  pub tref: Option<bool>,
+}
+
+impl<'a> RemoveEmptyTextPlugin<'a> {
+  pub fn new(config: RemoveEmptyTextPluginConfig, arena: &'a Bump) -> Self {
+    RemoveEmptyTextPlugin {
+      text: config.text.unwrap_or(true),
+      tspan: config.tspan.unwrap_or(true),
+      tref: config.tref.unwrap_or(true),
+      arena,
+    }
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveEmptyTextPlugin<'a> {
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    let remove = match el.name {
+      "text" => self.text && el.children.is_empty(),
+      "tspan" => self.tspan && el.children.is_empty(),
+      "tref" => self.tref && !el.has_attribute("xlink:href"),
+      _ => false,
+    };
+    if remove {
+      VisitAction::Remove
+    } else {
+      VisitAction::Keep
+    }
+  }
+}
@@ -1,6 +1,6 @@
 use bumpalo::Bump;
 
-use crate::optimizer::{Plugin, VisitAction};
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
 
 /// Remove <metadata>.
 #[allow(dead_code)]
@@ -17,7 +17,15 @@ impl<'a> RemoveMetadataPlugin<'a> {
 }
 
 impl<'a> Plugin<'a> for RemoveMetadataPlugin<'a> {
-  fn element_enter(&mut self, el: &mut crate::parser::XMLAstElement<'a>) -> VisitAction {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut crate::parser::XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     if el.name == "metadata" {
       VisitAction::Remove
     } else {
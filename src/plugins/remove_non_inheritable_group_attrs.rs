@@ -0,0 +1,44 @@
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+use crate::plugins::collections::{
+  INHERITABLE_ATTRS, PRESENTATION_ATTRS, PRESENTATION_NON_INHERITABLE_GROUP_ATTRS,
+};
+
+/// Removes non-inheritable group's presentational attributes.
+///
+/// ```svg
+/// <g vector-effect="non-scaling-stroke" stop-color="red" opacity="0.5">
+///              ⬇
+/// <g opacity="0.5">
+/// ```
+#[allow(dead_code)]
+pub struct RemoveNonInheritableGroupAttrsPlugin<'a> {
+  arena: &'a Bump,
+}
+
+pub struct RemoveNonInheritableGroupAttrsPluginConfig {}
+
+impl<'a> RemoveNonInheritableGroupAttrsPlugin<'a> {
+  pub fn new(_config: RemoveNonInheritableGroupAttrsPluginConfig, arena: &'a Bump) -> Self {
+    RemoveNonInheritableGroupAttrsPlugin { arena }
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveNonInheritableGroupAttrsPlugin<'a> {
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    if el.name == "g" {
+      el.attributes.retain(|(name, _)| {
+        !PRESENTATION_ATTRS.contains(name)
+          || INHERITABLE_ATTRS.contains(name)
+          || PRESENTATION_NON_INHERITABLE_GROUP_ATTRS.contains(name)
+      });
+    }
+    VisitAction::Keep
+  }
+}
@@ -0,0 +1,173 @@
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+use regex::Regex;
+
+use crate::optimizer::Plugin;
+use crate::parser::{XMLAstChild, XMLAstElement, XMLAstRoot};
+use crate::path_data::{
+  bounding_box, parse_path_data, to_segments, transform_segments, BoundingBox,
+};
+use crate::transform::{parse_transform, Matrix};
+
+/// Removes elements that are drawn outside of the viewBox.
+///
+/// ```svg
+/// <svg viewBox="0 0 100 100">
+///   <path d="M10 10h10v10z"/>
+///   <path d="M200 200h10v10z"/>
+/// </svg>
+///              ⬇
+/// <svg viewBox="0 0 100 100">
+///   <path d="M10 10h10v10z"/>
+/// </svg>
+/// ```
+///
+/// 包围盒会计算曲线的极值点，并应用 path 自身及所有祖先元素的 `transform`。
+/// 带 `id` 的 path 可能被引用，`<defs>`、`<clipPath>` 等容器中的内容不在画布上直接绘制，
+/// 嵌套的 `<svg>` 有自己的视口，这些都会被保留。
+#[allow(dead_code)]
+pub struct RemoveOffCanvasPathsPlugin<'a> {
+  arena: &'a Bump,
+}
+
+pub struct RemoveOffCanvasPathsPluginConfig {}
+
+/// 其中的内容不会直接绘制在画布上
+const NON_RENDERED_CONTAINERS: [&str; 6] =
+  ["clipPath", "defs", "marker", "mask", "pattern", "symbol"];
+
+/// 从祖先元素继承的、影响绘制范围的信息
+#[derive(Clone, Copy)]
+struct Context<'a> {
+  matrix: Matrix,
+  stroke: Option<&'a str>,
+  stroke_width: Option<&'a str>,
+}
+
+impl<'a> RemoveOffCanvasPathsPlugin<'a> {
+  pub fn new(_config: RemoveOffCanvasPathsPluginConfig, arena: &'a Bump) -> Self {
+    RemoveOffCanvasPathsPlugin { arena }
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveOffCanvasPathsPlugin<'a> {
+  fn root_exit(&mut self, root: &mut XMLAstRoot<'a>) {
+    let Some(svg) = root.children.iter_mut().find_map(|child| match child {
+      XMLAstChild::Element(el) if el.name == "svg" => Some(el),
+      _ => None,
+    }) else {
+      return;
+    };
+    let Some(view_box) = parse_view_box(svg) else {
+      return;
+    };
+    let context = Context {
+      matrix: Matrix::IDENTITY,
+      stroke: None,
+      stroke_width: None,
+    };
+    let Some(context) = apply_element(svg, &context) else {
+      return;
+    };
+    remove_paths(&mut svg.children, &context, &view_box);
+  }
+}
+
+fn remove_paths<'a>(
+  children: &mut BumpVec<'a, XMLAstChild<'a>>,
+  parent: &Context<'a>,
+  view_box: &BoundingBox,
+) {
+  children.retain_mut(|child| {
+    let XMLAstChild::Element(el) = child else {
+      return true;
+    };
+    if NON_RENDERED_CONTAINERS.contains(&el.name) {
+      return true;
+    }
+    // 嵌套的 <svg> 有自己的视口和坐标系，不计算其中内容的位置
+    if el.name == "svg" {
+      return true;
+    }
+    // transform 或样式无法确定时，保留整棵子树
+    let Some(context) = apply_element(el, parent) else {
+      return true;
+    };
+    if el.name == "path" {
+      return el.has_attribute("id") || is_visible(el, &context, view_box);
+    }
+    remove_paths(&mut el.children, &context, view_box);
+    true
+  });
+}
+
+/// 合并元素自身的 `transform` 和描边属性，返回 `None` 表示无法安全计算绘制范围
+fn apply_element<'a>(el: &XMLAstElement<'a>, parent: &Context<'a>) -> Option<Context<'a>> {
+  let mut context = *parent;
+  for (name, value) in el.attributes.iter() {
+    match *name {
+      "transform" => context.matrix = context.matrix.multiply(&parse_transform(value)?),
+      "stroke" => context.stroke = Some(value.trim()),
+      "stroke-width" => context.stroke_width = Some(value.trim()),
+      // 滤镜和 marker 会超出几何图形的范围；style 中的属性不做解析
+      "filter" | "marker-start" | "marker-mid" | "marker-end" | "style" => return None,
+      _ => {}
+    }
+  }
+  Some(context)
+}
+
+fn is_visible(el: &XMLAstElement, context: &Context, view_box: &BoundingBox) -> bool {
+  let Some(d) = el.get_attribute("d") else {
+    return true;
+  };
+  let mut segments = to_segments(&parse_path_data(d));
+  transform_segments(&mut segments, &context.matrix);
+  let Some(mut bbox) = bounding_box(&segments) else {
+    return true;
+  };
+
+  if context.stroke.is_some_and(|stroke| stroke != "none") {
+    let Some(width) = context.stroke_width.map_or(Some(1.0), parse_stroke_width) else {
+      return true;
+    };
+    // 尖角的延伸不超过 miterlimit（默认 4）倍的半线宽，按最大缩放比例放大
+    let Matrix { a, b, c, d, .. } = context.matrix;
+    let padding = width * 2.0 * (a * a + b * b + c * c + d * d).sqrt();
+    bbox.min_x -= padding;
+    bbox.min_y -= padding;
+    bbox.max_x += padding;
+    bbox.max_y += padding;
+  }
+  bbox.intersects(view_box)
+}
+
+fn parse_stroke_width(value: &str) -> Option<f64> {
+  value.strip_suffix("px").unwrap_or(value).parse().ok()
+}
+
+/// 读取根 `<svg>` 的 `viewBox`，没有时使用 `width` 和 `height`
+fn parse_view_box(svg: &XMLAstElement) -> Option<BoundingBox> {
+  let value = match svg.get_attribute("viewBox") {
+    Some(view_box) => view_box.to_string(),
+    None => format!(
+      "0 0 {} {}",
+      svg.get_attribute("width")?,
+      svg.get_attribute("height")?
+    ),
+  };
+  let separators = Regex::new(r"[,+]|px").unwrap();
+  let spaces = Regex::new(r"\s+").unwrap();
+  let normalized = separators.replace_all(&value, " ");
+  let normalized = spaces.replace_all(normalized.trim(), " ");
+  let pattern = Regex::new(r"^(-?\d*\.?\d+) (-?\d*\.?\d+) (\d*\.?\d+) (\d*\.?\d+)$").unwrap();
+  let captures = pattern.captures(&normalized)?;
+  let number = |i: usize| captures[i].parse::<f64>().ok();
+  let (left, top, width, height) = (number(1)?, number(2)?, number(3)?, number(4)?);
+  Some(BoundingBox {
+    min_x: left,
+    min_y: top,
+    max_x: left + width,
+    max_y: top + height,
+  })
+}
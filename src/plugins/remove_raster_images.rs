@@ -0,0 +1,52 @@
+use bumpalo::Bump;
+use regex::Regex;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+
+/// Remove raster images (JPEG, PNG and GIF), including embedded data URIs.
+///
+/// ```svg
+/// <image href="photo.jpg"/>
+/// <image xlink:href="data:image/png;base64,..."/>
+///              ⬇
+/// (removed)
+/// ```
+#[allow(dead_code)]
+pub struct RemoveRasterImagesPlugin<'a> {
+  raster_href: Regex,
+  arena: &'a Bump,
+}
+
+pub struct RemoveRasterImagesPluginConfig {}
+
+impl<'a> RemoveRasterImagesPlugin<'a> {
+  pub fn new(_config: RemoveRasterImagesPluginConfig, arena: &'a Bump) -> Self {
+    RemoveRasterImagesPlugin {
+      raster_href: Regex::new(r"(?i)(\.|image/)(jpe?g|png|gif)").unwrap(),
+      arena,
+    }
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveRasterImagesPlugin<'a> {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    let is_raster = el.name == "image"
+      && el.attributes.iter().any(|(name, value)| {
+        (*name == "href" || name.ends_with(":href")) && self.raster_href.is_match(value)
+      });
+    if is_raster {
+      VisitAction::Remove
+    } else {
+      VisitAction::Keep
+    }
+  }
+}
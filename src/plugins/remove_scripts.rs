@@ -0,0 +1,60 @@
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::{XMLAstChild, XMLAstElement};
+
+/// Remove scripts: `<script>` elements, `on*` event handler attributes and
+/// `javascript:` links.
+///
+/// ```svg
+/// <svg onload="init()">
+///   <script>alert(1)</script>
+///   <a href="javascript:alert(1)"><path d="M0 0"/></a>
+/// </svg>
+///              ⬇
+/// <svg>
+///   <path d="M0 0"/>
+/// </svg>
+/// ```
+///
+/// `<a href="javascript:...">` 会被替换为它的子元素，而不是连同内容一起删除。
+#[allow(dead_code)]
+pub struct RemoveScriptsPlugin<'a> {
+  arena: &'a Bump,
+}
+
+pub struct RemoveScriptsPluginConfig {}
+
+impl<'a> RemoveScriptsPlugin<'a> {
+  pub fn new(_config: RemoveScriptsPluginConfig, arena: &'a Bump) -> Self {
+    RemoveScriptsPlugin { arena }
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveScriptsPlugin<'a> {
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    if el.name == "script" {
+      return VisitAction::Remove;
+    }
+    if is_javascript_link_element(el) {
+      // 链接内只有空白的文本没有意义
+      el.children
+        .retain(|child| !matches!(child, XMLAstChild::Text(text) if text.value.trim().is_empty()));
+      return VisitAction::ReplaceWithChildren;
+    }
+    el.attributes.retain(|(name, _)| !name.to_ascii_lowercase().starts_with("on"));
+    VisitAction::Keep
+  }
+}
+
+fn is_javascript_link_element(el: &XMLAstElement) -> bool {
+  el.name == "a"
+    && el.attributes.iter().any(|(name, value)| {
+      (*name == "href" || name.ends_with(":href"))
+        && value.trim_start().to_ascii_lowercase().starts_with("javascript:")
+    })
+}
@@ -0,0 +1,36 @@
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+
+/// Remove `<style>` elements.
+#[allow(dead_code)]
+pub struct RemoveStyleElementPlugin<'a> {
+  arena: &'a Bump,
+}
+
+pub struct RemoveStyleElementPluginConfig {}
+
+impl<'a> RemoveStyleElementPlugin<'a> {
+  pub fn new(_config: RemoveStyleElementPluginConfig, arena: &'a Bump) -> Self {
+    RemoveStyleElementPlugin { arena }
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveStyleElementPlugin<'a> {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    if el.name == "style" {
+      VisitAction::Remove
+    } else {
+      VisitAction::Keep
+    }
+  }
+}
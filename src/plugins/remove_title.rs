@@ -1,6 +1,6 @@
 use bumpalo::Bump;
 
-use crate::optimizer::{Plugin, VisitAction};
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
 
 /// Remove <title>.
 #[allow(dead_code)]
@@ -17,7 +17,15 @@ impl<'a> RemoveTitlePlugin<'a> {
 }
 
 impl<'a> Plugin<'a> for RemoveTitlePlugin<'a> {
-  fn element_enter(&mut self, el: &mut crate::parser::XMLAstElement<'a>) -> VisitAction {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut crate::parser::XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     if el.name == "title" {
       VisitAction::Remove
     } else {
@@ -0,0 +1,80 @@
+use std::collections::HashSet;
+
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::{XMLAstChild, XMLAstElement, XMLAstRoot};
+
+/// Remove unused namespaces declaration from the root `<svg>`.
+///
+/// ```svg
+/// <svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+///   <path d="M0 0"/>
+/// </svg>
+///              ⬇
+/// <svg xmlns="http://www.w3.org/2000/svg">
+///   <path d="M0 0"/>
+/// </svg>
+/// ```
+#[allow(dead_code)]
+pub struct RemoveUnusedNSPlugin<'a> {
+  /// 根 `<svg>` 上声明但尚未发现使用的前缀
+  unused_namespaces: HashSet<&'a str>,
+  arena: &'a Bump,
+}
+
+pub struct RemoveUnusedNSPluginConfig {}
+
+impl<'a> RemoveUnusedNSPlugin<'a> {
+  pub fn new(_config: RemoveUnusedNSPluginConfig, arena: &'a Bump) -> Self {
+    RemoveUnusedNSPlugin {
+      unused_namespaces: HashSet::new(),
+      arena,
+    }
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveUnusedNSPlugin<'a> {
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    if el.name == "svg" && ctx.parent().is_none() {
+      for (name, _) in el.attributes.iter() {
+        if let Some(prefix) = name.strip_prefix("xmlns:") {
+          self.unused_namespaces.insert(prefix);
+        }
+      }
+    }
+    if self.unused_namespaces.is_empty() {
+      return VisitAction::Keep;
+    }
+    if let Some((prefix, _)) = el.name.split_once(':') {
+      self.unused_namespaces.remove(prefix);
+    }
+    for (name, _) in el.attributes.iter() {
+      if let Some((prefix, _)) = name.split_once(':') {
+        self.unused_namespaces.remove(prefix);
+      }
+    }
+    VisitAction::Keep
+  }
+
+  fn root_exit(&mut self, root: &mut XMLAstRoot<'a>) {
+    if self.unused_namespaces.is_empty() {
+      return;
+    }
+    let svg = root.children.iter_mut().find_map(|child| match child {
+      XMLAstChild::Element(el) if el.name == "svg" => Some(el),
+      _ => None,
+    });
+    if let Some(svg) = svg {
+      svg.attributes.retain(|(name, _)| {
+        name
+          .strip_prefix("xmlns:")
+          .is_none_or(|prefix| !self.unused_namespaces.contains(prefix))
+      });
+    }
+  }
+}
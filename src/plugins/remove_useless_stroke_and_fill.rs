@@ -0,0 +1,174 @@
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+use phf::{phf_set, Set};
+
+use crate::optimizer::Plugin;
+use crate::parser::{XMLAstChild, XMLAstElement, XMLAstRoot};
+
+/// Remove useless `stroke` and `fill` attributes.
+///
+/// ```svg
+/// <path d="M0 0" stroke="none" stroke-width="2" fill="red" fill-opacity="0"/>
+///              ⬇
+/// <path d="M0 0" fill="none"/>
+/// ```
+///
+/// 判断依据是继承后的计算值：父元素设置了 `stroke` 时，子元素会显式写上
+/// `stroke="none"` 以免继承。文档中存在 `<style>` 或脚本时不做处理。
+#[allow(dead_code)]
+pub struct RemoveUselessStrokeAndFillPlugin<'a> {
+  stroke: bool,
+  fill: bool,
+  remove_none: bool,
+  arena: &'a Bump,
+}
+
+pub struct RemoveUselessStrokeAndFillPluginConfig {
+  /// 删除无用的 `stroke*` 属性，默认 `true`
+  pub stroke: Option<bool>,
+  /// 删除无用的 `fill-*` 属性，默认 `true`
+  pub fill: Option<bool>,
+  /// 删除 `stroke` 和 `fill` 都为 `none` 的图形，默认 `false`
+  pub remove_none: Option<bool>,
+}
+
+static SHAPE_ELEMS: Set<&'static str> = phf_set! {
+  "circle",
+  "ellipse",
+  "line",
+  "path",
+  "polygon",
+  "polyline",
+  "rect",
+};
+
+/// 本插件关心的属性，它们都是可继承的
+const TRACKED_PROPS: [&str; 6] = [
+  "stroke",
+  "stroke-opacity",
+  "stroke-width",
+  "marker-end",
+  "fill",
+  "fill-opacity",
+];
+
+/// 继承后的计算值，下标与 `TRACKED_PROPS` 对应
+type ComputedStyle<'a> = [Option<&'a str>; 6];
+
+impl<'a> RemoveUselessStrokeAndFillPlugin<'a> {
+  pub fn new(config: RemoveUselessStrokeAndFillPluginConfig, arena: &'a Bump) -> Self {
+    RemoveUselessStrokeAndFillPlugin {
+      stroke: config.stroke.unwrap_or(true),
+      fill: config.fill.unwrap_or(true),
+      remove_none: config.remove_none.unwrap_or(false),
+      arena,
+    }
+  }
+
+  fn process_children(
+    &self,
+    children: &mut BumpVec<'a, XMLAstChild<'a>>,
+    parent_style: Option<&ComputedStyle<'a>>,
+  ) {
+    let mut i = 0;
+    while i < children.len() {
+      if let XMLAstChild::Element(el) = &mut children[i] {
+        // 带 id 的元素可能被引用，跳过整棵子树
+        if el.has_attribute("id") {
+          i += 1;
+          continue;
+        }
+        let style = compute_style(el, parent_style);
+        if SHAPE_ELEMS.contains(el.name) && self.process_shape(el, &style, parent_style) {
+          children.remove(i);
+          continue;
+        }
+        self.process_children(&mut el.children, Some(&style));
+      }
+      i += 1;
+    }
+  }
+
+  /// 返回 `true` 表示该图形应当被删除
+  fn process_shape(
+    &self,
+    el: &mut XMLAstElement<'a>,
+    style: &ComputedStyle<'a>,
+    parent_style: Option<&ComputedStyle<'a>>,
+  ) -> bool {
+    let [stroke, stroke_opacity, stroke_width, marker_end, fill, fill_opacity] = *style;
+    let parent_stroke = parent_style.and_then(|parent| parent[0]);
+
+    if self.stroke
+      && (stroke.is_none_or(|v| v == "none")
+        || stroke_opacity == Some("0")
+        || stroke_width == Some("0"))
+      // stroke-width 会影响 marker-end 的大小，为 0 时 marker 不可见
+      && (stroke_width == Some("0") || marker_end.is_none())
+    {
+      el.attributes.retain(|(name, _)| !name.starts_with("stroke"));
+      // 显式设置为 none，避免继承父元素的 stroke
+      if parent_stroke.is_some_and(|v| v != "none") {
+        el.attributes.push(("stroke", "none"));
+      }
+    }
+
+    if self.fill && (fill == Some("none") || fill_opacity == Some("0")) {
+      el.attributes.retain(|(name, _)| !name.starts_with("fill-"));
+      if fill.is_none_or(|v| v != "none") {
+        el.set_attribute("fill", "none");
+      }
+    }
+
+    self.remove_none
+      && (stroke.is_none() || el.get_attribute("stroke") == Some("none"))
+      && (fill == Some("none") || el.get_attribute("fill") == Some("none"))
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveUselessStrokeAndFillPlugin<'a> {
+  fn root_exit(&mut self, root: &mut XMLAstRoot<'a>) {
+    if has_style_or_script(&root.children) {
+      return;
+    }
+    self.process_children(&mut root.children, None);
+  }
+}
+
+/// 计算元素的样式：先继承父元素，再依次应用表现属性和 `style` 属性
+fn compute_style<'a>(
+  el: &XMLAstElement<'a>,
+  parent_style: Option<&ComputedStyle<'a>>,
+) -> ComputedStyle<'a> {
+  let mut style = parent_style.copied().unwrap_or_default();
+  let mut apply = |name: &str, value: &'a str| {
+    if let Some(index) = TRACKED_PROPS.iter().position(|prop| *prop == name) {
+      style[index] = Some(value);
+    }
+  };
+  for (name, value) in el.attributes.iter() {
+    apply(name, value.trim());
+  }
+  if let Some(style_attr) = el.get_attribute("style") {
+    for declaration in style_attr.split(';') {
+      if let Some((name, value)) = declaration.split_once(':') {
+        let value = value.trim();
+        let value = value.strip_suffix("!important").unwrap_or(value).trim_end();
+        apply(name.trim(), value);
+      }
+    }
+  }
+  style
+}
+
+fn has_style_or_script(children: &[XMLAstChild]) -> bool {
+  children.iter().any(|child| match child {
+    XMLAstChild::Element(el) => {
+      el.name == "style"
+        || el.name == "script"
+        || el.attributes.iter().any(|(name, _)| name.starts_with("on"))
+        || has_style_or_script(&el.children)
+    }
+    _ => false,
+  })
+}
@@ -0,0 +1,61 @@
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+use crate::plugins::remove_dimensions::parse_length;
+
+/// Remove `viewBox` from the root `<svg>` when it coincides with `width`/`height`.
+///
+/// ```svg
+/// <svg width="100" height="50" viewBox="0 0 100 50"/>
+///              ⬇
+/// <svg width="100" height="50"/>
+/// ```
+///
+/// 与 `removeDimensions` 互斥：两者同时启用时只保留 `removeDimensions`。
+#[allow(dead_code)]
+pub struct RemoveViewBoxPlugin<'a> {
+  arena: &'a Bump,
+}
+
+pub struct RemoveViewBoxPluginConfig {}
+
+impl<'a> RemoveViewBoxPlugin<'a> {
+  pub fn new(_config: RemoveViewBoxPluginConfig, arena: &'a Bump) -> Self {
+    RemoveViewBoxPlugin { arena }
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveViewBoxPlugin<'a> {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    if el.name != "svg" || ctx.parent().is_some() {
+      return VisitAction::Keep;
+    }
+
+    let (Some(view_box), Some(width), Some(height)) = (
+      el.get_attribute("viewBox"),
+      el.get_attribute("width").and_then(parse_length),
+      el.get_attribute("height").and_then(parse_length),
+    ) else {
+      return VisitAction::Keep;
+    };
+
+    let nums: Vec<Option<f64>> = view_box
+      .split(|c: char| c == ',' || c.is_whitespace())
+      .filter(|s| !s.is_empty())
+      .map(|s| s.parse::<f64>().ok())
+      .collect();
+    if nums == [Some(0.0), Some(0.0), Some(width), Some(height)] {
+      el.remove_attribute("viewBox");
+    }
+    VisitAction::Keep
+  }
+}
@@ -0,0 +1,170 @@
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::{XMLAstChild, XMLAstElement, XMLAstText};
+
+const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+
+/// 不支持 SVG 2 `href` 的元素
+const LEGACY_ELEMENTS: [&str; 5] = ["cursor", "filter", "font-face-uri", "glyphRef", "tref"];
+
+/// Remove XLink namespace and replace attributes with the SVG 2 equivalent where applicable.
+///
+/// ```svg
+/// <svg xmlns:xlink="http://www.w3.org/1999/xlink">
+///   <a xlink:href="#a" xlink:show="new" xlink:title="Title"><use xlink:href="#b"/></a>
+/// </svg>
+///              ⬇
+/// <svg>
+///   <a href="#a" target="_blank"><title>Title</title><use href="#b"/></a>
+/// </svg>
+/// ```
+///
+/// - `xlink:href` 改为 `href`
+/// - `xlink:show` 改为 `target`
+/// - `xlink:title` 改为 `<title>` 子元素
+pub struct RemoveXlinkPlugin<'a> {
+  include_legacy: bool,
+  /// 当前作用域内指向 xlink 命名空间的前缀
+  xlink_prefixes: Vec<&'a str>,
+  /// 在子元素上被重新声明为其它命名空间的 xlink 前缀
+  overridden_prefixes: Vec<&'a str>,
+  /// 在 legacy 元素上仍需使用的前缀，它们的声明必须保留
+  used_in_legacy_element: Vec<&'a str>,
+  arena: &'a Bump,
+}
+
+pub struct RemoveXlinkPluginConfig {
+  /// 同时改写 `<filter>`、`<tref>` 等不支持 SVG 2 `href` 的元素，默认 `false`
+  pub include_legacy: Option<bool>,
+}
+
+impl<'a> RemoveXlinkPlugin<'a> {
+  pub fn new(config: RemoveXlinkPluginConfig, arena: &'a Bump) -> Self {
+    RemoveXlinkPlugin {
+      include_legacy: config.include_legacy.unwrap_or(false),
+      xlink_prefixes: vec![],
+      overridden_prefixes: vec![],
+      used_in_legacy_element: vec![],
+      arena,
+    }
+  }
+
+  /// 找出 `<prefix>:<local_name>` 形式的属性名
+  fn find_prefixed_attrs(&self, el: &XMLAstElement<'a>, local_name: &str) -> Vec<&'a str> {
+    let xlink_prefixes = &self.xlink_prefixes;
+    el.attributes
+      .iter()
+      .filter(|(key, _)| {
+        key
+          .split_once(':')
+          .is_some_and(|(prefix, name)| name == local_name && xlink_prefixes.contains(&prefix))
+      })
+      .map(|(key, _)| *key)
+      .collect()
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveXlinkPlugin<'a> {
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    for (key, value) in el.attributes.iter() {
+      if let Some(prefix) = key.strip_prefix("xmlns:") {
+        if *value == XLINK_NAMESPACE {
+          self.xlink_prefixes.push(prefix);
+        } else if self.xlink_prefixes.contains(&prefix) {
+          self.overridden_prefixes.push(prefix);
+        }
+      }
+    }
+
+    let overridden = &self.overridden_prefixes;
+    if overridden.iter().any(|prefix| self.xlink_prefixes.contains(prefix)) {
+      return VisitAction::Keep;
+    }
+
+    // xlink:show -> target
+    let mut show_handled = el.has_attribute("target");
+    for attr in self.find_prefixed_attrs(el, "show").into_iter().rev() {
+      let target = match el.get_attribute(attr) {
+        Some("new") => Some("_blank"),
+        Some("replace") => Some("_self"),
+        _ => None,
+      };
+      el.remove_attribute(attr);
+      if let (false, Some(target)) = (show_handled, target) {
+        el.set_attribute("target", target);
+        show_handled = true;
+      }
+    }
+
+    // xlink:title -> <title>
+    for attr in self.find_prefixed_attrs(el, "title").into_iter().rev() {
+      let has_title = el
+        .children
+        .iter()
+        .any(|child| matches!(child, XMLAstChild::Element(child) if child.name == "title"));
+      if let (false, Some(value)) = (has_title, el.get_attribute(attr)) {
+        let mut children = BumpVec::new_in(self.arena);
+        children.push(XMLAstChild::Text(XMLAstText { value }));
+        el.children.insert(
+          0,
+          XMLAstChild::Element(XMLAstElement {
+            name: "title",
+            attributes: BumpVec::new_in(self.arena),
+            children,
+          }),
+        );
+      }
+      el.remove_attribute(attr);
+    }
+
+    // xlink:href -> href
+    let href_attrs = self.find_prefixed_attrs(el, "href");
+    if !href_attrs.is_empty() && LEGACY_ELEMENTS.contains(&el.name) && !self.include_legacy {
+      for attr in href_attrs {
+        if let Some((prefix, _)) = attr.split_once(':') {
+          self.used_in_legacy_element.push(prefix);
+        }
+      }
+      return VisitAction::Keep;
+    }
+    for attr in href_attrs.into_iter().rev() {
+      if let (false, Some(value)) = (el.has_attribute("href"), el.get_attribute(attr)) {
+        el.set_attribute("href", value);
+      }
+      el.remove_attribute(attr);
+    }
+    VisitAction::Keep
+  }
+
+  fn element_exit(&mut self, el: &mut XMLAstElement<'a>, _ctx: &VisitContext<'_, 'a>) {
+    let xlink_prefixes = &mut self.xlink_prefixes;
+    let overridden_prefixes = &mut self.overridden_prefixes;
+    el.attributes.retain(|(key, value)| {
+      let (prefix, local_name) = key.split_once(':').unwrap_or(("", key));
+      if xlink_prefixes.contains(&prefix)
+        && !overridden_prefixes.contains(&prefix)
+        && !self.used_in_legacy_element.contains(&prefix)
+      {
+        return false;
+      }
+      if prefix == "xmlns" && !self.used_in_legacy_element.contains(&local_name) {
+        if *value == XLINK_NAMESPACE {
+          if let Some(index) = xlink_prefixes.iter().position(|p| *p == local_name) {
+            xlink_prefixes.remove(index);
+          }
+          return false;
+        }
+        if let Some(index) = overridden_prefixes.iter().position(|p| *p == local_name) {
+          overridden_prefixes.remove(index);
+        }
+      }
+      true
+    });
+  }
+}
@@ -1,6 +1,6 @@
 use bumpalo::Bump;
 
-use crate::optimizer::{Plugin, VisitAction};
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
 
 /// Remove XML Processing Instruction.
 #[allow(dead_code)]
@@ -17,7 +17,15 @@ impl<'a> RemoveXMLProcInstPlugin<'a> {
 }
 
 impl<'a> Plugin<'a> for RemoveXMLProcInstPlugin<'a> {
-  fn decl_enter(&self, _el: &mut crate::parser::XMLAstDecl<'a>) -> VisitAction {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn decl_enter(
+    &mut self,
+    _el: &mut crate::parser::XMLAstDecl<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
     VisitAction::Remove
   }
 }
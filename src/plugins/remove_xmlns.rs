@@ -0,0 +1,41 @@
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+
+/// Remove `xmlns` attribute when present, useful for inline SVG in HTML.
+///
+/// ```svg
+/// <svg xmlns="http://www.w3.org/2000/svg"/>
+///              ⬇
+/// <svg/>
+/// ```
+#[allow(dead_code)]
+pub struct RemoveXMLNSPlugin<'a> {
+  arena: &'a Bump,
+}
+
+pub struct RemoveXMLNSPluginConfig {}
+
+impl<'a> RemoveXMLNSPlugin<'a> {
+  pub fn new(_config: RemoveXMLNSPluginConfig, arena: &'a Bump) -> Self {
+    RemoveXMLNSPlugin { arena }
+  }
+}
+
+impl<'a> Plugin<'a> for RemoveXMLNSPlugin<'a> {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    if el.name == "svg" {
+      el.remove_attribute("xmlns");
+    }
+    VisitAction::Keep
+  }
+}
@@ -0,0 +1,314 @@
+use std::collections::{HashMap, HashSet};
+
+use bumpalo::collections::Vec as BumpVec;
+use bumpalo::Bump;
+
+use crate::optimizer::Plugin;
+use crate::parser::{XMLAstChild, XMLAstElement, XMLAstRoot};
+
+const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+
+/// Finds `<path>` elements with the same `d`, `fill` and `stroke`, moves one
+/// copy into `<defs>` and replaces every occurrence with a `<use>` referencing it.
+///
+/// ```svg
+/// <svg>
+///   <path d="M0 0h10" fill="red"/>
+///   <path d="M0 0h10" fill="red" transform="translate(10)"/>
+/// </svg>
+///              ⬇
+/// <svg xmlns:xlink="http://www.w3.org/1999/xlink">
+///   <defs><path d="M0 0h10" fill="red" id="reuse-0"/></defs>
+///   <use xlink:href="#reuse-0"/>
+///   <use transform="translate(10)" xlink:href="#reuse-0"/>
+/// </svg>
+/// ```
+///
+/// 只有在替换后输出确实变短时才会改写。
+pub struct ReusePathsPlugin<'a> {
+  arena: &'a Bump,
+}
+
+pub struct ReusePathsPluginConfig {}
+
+impl<'a> ReusePathsPlugin<'a> {
+  pub fn new(_config: ReusePathsPluginConfig, arena: &'a Bump) -> Self {
+    ReusePathsPlugin { arena }
+  }
+}
+
+/// 判断 path 是否相同的依据：`d`、`fill`、`stroke`
+type PathKey<'a> = (&'a str, Option<&'a str>, Option<&'a str>);
+
+/// 一组相同的 path
+struct PathGroup<'a> {
+  key: PathKey<'a>,
+  count: usize,
+  /// 第一个 path 的 id
+  first_id: Option<&'a str>,
+}
+
+/// 第一次遍历收集到的信息
+#[derive(Default)]
+struct Collected<'a> {
+  groups: Vec<PathGroup<'a>>,
+  group_index: HashMap<PathKey<'a>, usize>,
+  ids: HashSet<&'a str>,
+  /// 被 `href`、`url(#...)` 或 `<style>` 中的 `#id` 选择器引用的 id
+  referenced_ids: HashSet<&'a str>,
+}
+
+/// 最终决定复用的 path
+struct Reusable<'a> {
+  id: &'a str,
+  /// 复用了第一个 path 原有的 id，需要把它从 `<use>` 上移除
+  moved_id: bool,
+}
+
+impl<'a> Plugin<'a> for ReusePathsPlugin<'a> {
+  fn root_exit(&mut self, root: &mut XMLAstRoot<'a>) {
+    let Some(svg) = root.children.iter_mut().find_map(|child| match child {
+      XMLAstChild::Element(el) if el.name == "svg" => Some(el),
+      _ => None,
+    }) else {
+      return;
+    };
+
+    let mut collected = Collected::default();
+    collect(&svg.children, false, &mut collected);
+
+    let has_defs = svg
+      .children
+      .iter()
+      .any(|child| matches!(child, XMLAstChild::Element(el) if el.name == "defs"));
+    let needs_xlink = svg.get_attribute("xmlns:xlink").is_none();
+
+    let mut reusables: HashMap<PathKey<'a>, Reusable<'a>> = HashMap::new();
+    let mut defs_paths: Vec<XMLAstChild<'a>> = Vec::new();
+    let mut total_savings: isize = 0;
+    let mut next_id = 0;
+    for group in collected.groups.iter().filter(|group| group.count > 1) {
+      let (id, moved_id) = match group.first_id {
+        Some(id) if !collected.referenced_ids.contains(id) => (id, true),
+        _ => loop {
+          let id = bumpalo::format!(in self.arena, "reuse-{}", next_id).into_bump_str();
+          next_id += 1;
+          if !collected.ids.contains(id) {
+            break (id, false);
+          }
+        },
+      };
+
+      let savings = estimate_savings(group, id, moved_id);
+      if savings <= 0 {
+        continue;
+      }
+      total_savings += savings;
+
+      let (d, fill, stroke) = group.key;
+      let mut attributes = BumpVec::new_in(self.arena);
+      if let Some(fill) = fill {
+        attributes.push(("fill", fill));
+      }
+      if let Some(stroke) = stroke {
+        attributes.push(("stroke", stroke));
+      }
+      attributes.push(("d", d));
+      attributes.push(("id", id));
+      defs_paths.push(XMLAstChild::Element(XMLAstElement {
+        name: "path",
+        attributes,
+        children: BumpVec::new_in(self.arena),
+      }));
+      reusables.insert(group.key, Reusable { id, moved_id });
+    }
+
+    // 新建 `<defs>` 和声明 xlink 命名空间的开销
+    let mut overhead = 0;
+    if !has_defs {
+      overhead += "<defs></defs>".len() as isize;
+    }
+    if needs_xlink {
+      overhead += format!(" xmlns:xlink=\"{}\"", XLINK_NAMESPACE).len() as isize;
+    }
+    if reusables.is_empty() || total_savings <= overhead {
+      return;
+    }
+
+    replace_paths(&mut svg.children, false, &reusables, self.arena);
+
+    if needs_xlink {
+      svg.attributes.push(("xmlns:xlink", XLINK_NAMESPACE));
+    }
+    let defs = svg.children.iter_mut().find_map(|child| match child {
+      XMLAstChild::Element(el) if el.name == "defs" => Some(el),
+      _ => None,
+    });
+    match defs {
+      Some(defs) => defs.children.extend(defs_paths),
+      None => {
+        let mut children = BumpVec::new_in(self.arena);
+        children.extend(defs_paths);
+        svg.children.insert(
+          0,
+          XMLAstChild::Element(XMLAstElement {
+            name: "defs",
+            attributes: BumpVec::new_in(self.arena),
+            children,
+          }),
+        );
+      }
+    }
+  }
+}
+
+fn collect<'a>(children: &[XMLAstChild<'a>], in_defs: bool, collected: &mut Collected<'a>) {
+  for child in children {
+    let XMLAstChild::Element(el) = child else {
+      continue;
+    };
+    for (name, value) in el.attributes.iter() {
+      match *name {
+        "id" => {
+          collected.ids.insert(value);
+        }
+        "href" | "xlink:href" => {
+          if let Some(id) = value.strip_prefix('#') {
+            collected.referenced_ids.insert(id);
+          }
+        }
+        _ => collect_url_references(value, &mut collected.referenced_ids),
+      }
+    }
+
+    if el.name == "style" {
+      for child in el.children.iter() {
+        match child {
+          XMLAstChild::Text(text) => {
+            collect_id_selectors(text.value, &mut collected.referenced_ids)
+          }
+          XMLAstChild::Cdata(cdata) => {
+            collect_id_selectors(cdata.value, &mut collected.referenced_ids)
+          }
+          _ => {}
+        }
+      }
+    }
+
+    if is_reusable(el, in_defs) {
+      if let Some(d) = el.get_attribute("d") {
+        let key = (d, el.get_attribute("fill"), el.get_attribute("stroke"));
+        match collected.group_index.get(&key) {
+          Some(&index) => collected.groups[index].count += 1,
+          None => {
+            collected.group_index.insert(key, collected.groups.len());
+            collected.groups.push(PathGroup {
+              key,
+              count: 1,
+              first_id: el.get_attribute("id"),
+            });
+          }
+        }
+      }
+    }
+
+    collect(&el.children, in_defs || el.name == "defs", collected);
+  }
+}
+
+/// 与 svgo 相同，有子节点（如 `<title>`、`<animate>`）的 path 不复用，否则子节点会丢失
+fn is_reusable(el: &XMLAstElement, in_defs: bool) -> bool {
+  el.name == "path" && !in_defs && el.children.is_empty()
+}
+
+/// 收集 `url(#id)`、`url('#id')` 和 `url("#id")` 引用的 id
+fn collect_url_references<'a>(value: &'a str, referenced_ids: &mut HashSet<&'a str>) {
+  let mut rest = value;
+  while let Some(start) = rest.find("url(") {
+    rest = rest[start + "url(".len()..].trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '\'' || *c == '"');
+    if let Some(quote) = quote {
+      rest = &rest[1..];
+      let end = rest.find(quote).unwrap_or(rest.len());
+      if let Some(id) = rest[..end].strip_prefix('#') {
+        referenced_ids.insert(id);
+      }
+      rest = &rest[end..];
+    } else {
+      let end = rest.find(')').unwrap_or(rest.len());
+      if let Some(id) = rest[..end].trim_end().strip_prefix('#') {
+        referenced_ids.insert(id);
+      }
+      rest = &rest[end..];
+    }
+  }
+}
+
+/// 收集样式表中 `#id` 形式的 id，颜色值等也会被当成 id，只会让 path 改用新的 id
+fn collect_id_selectors<'a>(css: &'a str, referenced_ids: &mut HashSet<&'a str>) {
+  for (start, _) in css.match_indices('#') {
+    let rest = &css[start + 1..];
+    let end = rest
+      .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+      .unwrap_or(rest.len());
+    if end > 0 {
+      referenced_ids.insert(&rest[..end]);
+    }
+  }
+}
+
+fn replace_paths<'a>(
+  children: &mut [XMLAstChild<'a>],
+  in_defs: bool,
+  reusables: &HashMap<PathKey<'a>, Reusable<'a>>,
+  arena: &'a Bump,
+) {
+  for child in children.iter_mut() {
+    let XMLAstChild::Element(el) = child else {
+      continue;
+    };
+    if is_reusable(el, in_defs) {
+      if let Some(d) = el.get_attribute("d") {
+        let key = (d, el.get_attribute("fill"), el.get_attribute("stroke"));
+        if let Some(reusable) = reusables.get(&key) {
+          el.attributes.retain(|(name, value)| match *name {
+            "d" | "fill" | "stroke" => false,
+            "id" => !(reusable.moved_id && *value == reusable.id),
+            _ => true,
+          });
+          el.name = "use";
+          let href = bumpalo::format!(in arena, "#{}", reusable.id).into_bump_str();
+          el.attributes.push(("xlink:href", href));
+        }
+      }
+    }
+    replace_paths(
+      &mut el.children,
+      in_defs || el.name == "defs",
+      reusables,
+      arena,
+    );
+  }
+}
+
+/// 估算一组 path 改写为 `<use>` 后节省的字节数
+fn estimate_savings(group: &PathGroup, id: &str, moved_id: bool) -> isize {
+  let (d, fill, stroke) = group.key;
+  let attr_len = |name: &str, value: Option<&str>| {
+    value.map_or(0, |value| name.len() + value.len() + 4) as isize
+  };
+  let shared_len = attr_len("d", Some(d)) + attr_len("fill", fill) + attr_len("stroke", stroke);
+  let id_len = attr_len("id", Some(id));
+  let href_len = attr_len("xlink:href", Some(id)) + 1;
+  let count = group.count as isize;
+
+  // `<path .../>` 放进 `<defs>`
+  let mut cost = "<path/>".len() as isize + shared_len + id_len;
+  // 每个 `<use>` 都要引用它；`path` 改名为 `use` 少一个字符
+  cost += count * (href_len - 1);
+  let mut saved = count * shared_len;
+  if moved_id {
+    saved += id_len;
+  }
+  saved - cost
+}
@@ -0,0 +1,105 @@
+use std::cmp::Ordering;
+
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::XMLAstElement;
+
+/// Sort element attributes for better compression.
+///
+/// ```svg
+/// <rect fill="red" height="10" width="10" id="a"/>
+///              ⬇
+/// <rect id="a" width="10" height="10" fill="red"/>
+/// ```
+///
+/// 属性按 `order` 中的顺序排列，`fill-rule` 等带连字符的属性跟随其前缀 `fill`，
+/// 其余属性按字母顺序排在后面。
+#[allow(dead_code)]
+pub struct SortAttrsPlugin<'a> {
+  order: Vec<String>,
+  xmlns_order: XmlnsOrder,
+  arena: &'a Bump,
+}
+
+/// 命名空间属性的排序方式
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum XmlnsOrder {
+  /// `xmlns` 和 `xmlns:*` 排在最前面
+  Front,
+  /// 与普通属性一起按字母顺序排序
+  Alphabetical,
+}
+
+pub struct SortAttrsPluginConfig {
+  /// 自定义属性顺序，默认与 svgo 相同
+  pub order: Option<Vec<String>>,
+  pub xmlns_order: Option<XmlnsOrder>,
+}
+
+const DEFAULT_ORDER: [&str; 17] = [
+  "id", "width", "height", "x", "x1", "x2", "y", "y1", "y2", "cx", "cy", "r", "fill", "stroke",
+  "marker", "d", "points",
+];
+
+impl<'a> SortAttrsPlugin<'a> {
+  pub fn new(config: SortAttrsPluginConfig, arena: &'a Bump) -> Self {
+    SortAttrsPlugin {
+      order: config
+        .order
+        .unwrap_or_else(|| DEFAULT_ORDER.iter().map(|name| name.to_string()).collect()),
+      xmlns_order: config.xmlns_order.unwrap_or(XmlnsOrder::Front),
+      arena,
+    }
+  }
+
+  fn ns_priority(&self, name: &str) -> u8 {
+    if self.xmlns_order == XmlnsOrder::Front {
+      if name == "xmlns" {
+        return 3;
+      }
+      if name.starts_with("xmlns:") {
+        return 2;
+      }
+    }
+    if name.contains(':') {
+      return 1;
+    }
+    0
+  }
+
+  fn compare(&self, a: &str, b: &str) -> Ordering {
+    let priority = self.ns_priority(b).cmp(&self.ns_priority(a));
+    if priority != Ordering::Equal {
+      return priority;
+    }
+    let a_part = a.split('-').next().unwrap_or(a);
+    let b_part = b.split('-').next().unwrap_or(b);
+    if a_part != b_part {
+      let a_index = self.order.iter().position(|name| name == a_part);
+      let b_index = self.order.iter().position(|name| name == b_part);
+      match (a_index, b_index) {
+        (Some(a_index), Some(b_index)) => return a_index.cmp(&b_index),
+        (Some(_), None) => return Ordering::Less,
+        (None, Some(_)) => return Ordering::Greater,
+        (None, None) => {}
+      }
+    }
+    a.cmp(b)
+  }
+}
+
+impl<'a> Plugin<'a> for SortAttrsPlugin<'a> {
+  fn is_node_local(&self) -> bool {
+    true
+  }
+
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    el.attributes.sort_by(|(a, _), (b, _)| self.compare(a, b));
+    VisitAction::Keep
+  }
+}
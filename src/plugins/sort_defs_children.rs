@@ -0,0 +1,67 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use bumpalo::Bump;
+
+use crate::optimizer::{Plugin, VisitAction, VisitContext};
+use crate::parser::{XMLAstChild, XMLAstElement};
+
+/// Sorts children of `<defs>` to improve compression.
+///
+/// 出现次数多的元素排在前面，次数相同时按标签名长度、再按标签名排序，
+/// 文本和注释等非元素节点保持原位。
+#[allow(dead_code)]
+pub struct SortDefsChildrenPlugin<'a> {
+  arena: &'a Bump,
+}
+
+pub struct SortDefsChildrenPluginConfig {}
+
+impl<'a> SortDefsChildrenPlugin<'a> {
+  pub fn new(_config: SortDefsChildrenPluginConfig, arena: &'a Bump) -> Self {
+    SortDefsChildrenPlugin { arena }
+  }
+}
+
+impl<'a> Plugin<'a> for SortDefsChildrenPlugin<'a> {
+  fn element_enter(
+    &mut self,
+    el: &mut XMLAstElement<'a>,
+    _ctx: &VisitContext<'_, 'a>,
+  ) -> VisitAction<'a> {
+    if el.name != "defs" {
+      return VisitAction::Keep;
+    }
+
+    // 元素取出来排序，其它节点留在原来的位置
+    let mut frequencies: HashMap<&str, usize> = HashMap::new();
+    let mut elements: Vec<XMLAstElement<'a>> = Vec::new();
+    let mut layout: Vec<Option<XMLAstChild<'a>>> = Vec::new();
+    for child in el.children.drain(..) {
+      match child {
+        XMLAstChild::Element(child_el) => {
+          *frequencies.entry(child_el.name).or_default() += 1;
+          elements.push(child_el);
+          layout.push(None);
+        }
+        other => layout.push(Some(other)),
+      }
+    }
+
+    elements.sort_by_key(|child_el| {
+      (
+        Reverse(frequencies[child_el.name]),
+        Reverse(child_el.name.len()),
+        Reverse(child_el.name),
+      )
+    });
+    let mut elements = elements.into_iter();
+    for slot in layout {
+      match slot {
+        Some(other) => el.children.push(other),
+        None => el.children.extend(elements.next().map(XMLAstChild::Element)),
+      }
+    }
+    VisitAction::Keep
+  }
+}
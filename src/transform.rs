@@ -0,0 +1,198 @@
+use crate::path_data::parse_number_list;
+
+/// 二维仿射变换矩阵 `[a b c d e f]`，与 SVG 的 `matrix(a, b, c, d, e, f)` 含义相同
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix {
+  pub a: f64,
+  pub b: f64,
+  pub c: f64,
+  pub d: f64,
+  pub e: f64,
+  pub f: f64,
+}
+
+impl Matrix {
+  pub const IDENTITY: Matrix = Matrix {
+    a: 1.0,
+    b: 0.0,
+    c: 0.0,
+    d: 1.0,
+    e: 0.0,
+    f: 0.0,
+  };
+
+  pub fn translate(tx: f64, ty: f64) -> Self {
+    Matrix {
+      e: tx,
+      f: ty,
+      ..Self::IDENTITY
+    }
+  }
+
+  pub fn scale(sx: f64, sy: f64) -> Self {
+    Matrix {
+      a: sx,
+      d: sy,
+      ..Self::IDENTITY
+    }
+  }
+
+  /// 旋转，角度单位为度
+  pub fn rotate(angle: f64) -> Self {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    Matrix {
+      a: cos,
+      b: sin,
+      c: -sin,
+      d: cos,
+      e: 0.0,
+      f: 0.0,
+    }
+  }
+
+  pub fn skew_x(angle: f64) -> Self {
+    Matrix {
+      c: angle.to_radians().tan(),
+      ..Self::IDENTITY
+    }
+  }
+
+  pub fn skew_y(angle: f64) -> Self {
+    Matrix {
+      b: angle.to_radians().tan(),
+      ..Self::IDENTITY
+    }
+  }
+
+  /// `self × other`：先应用 `other`，再应用 `self`
+  pub fn multiply(&self, other: &Matrix) -> Matrix {
+    Matrix {
+      a: self.a * other.a + self.c * other.b,
+      b: self.b * other.a + self.d * other.b,
+      c: self.a * other.c + self.c * other.d,
+      d: self.b * other.c + self.d * other.d,
+      e: self.a * other.e + self.c * other.f + self.e,
+      f: self.b * other.e + self.d * other.f + self.f,
+    }
+  }
+
+  pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+    (
+      self.a * x + self.c * y + self.e,
+      self.b * x + self.d * y + self.f,
+    )
+  }
+
+  /// 只应用线性部分，用于相对坐标
+  pub fn apply_linear(&self, x: f64, y: f64) -> (f64, f64) {
+    (self.a * x + self.c * y, self.b * x + self.d * y)
+  }
+
+  pub fn determinant(&self) -> f64 {
+    self.a * self.d - self.b * self.c
+  }
+}
+
+/// 解析 `transform` 属性，多个变换按从左到右的顺序相乘。
+///
+/// 语法错误时返回 `None`。
+pub fn parse_transform(value: &str) -> Option<Matrix> {
+  let mut matrix = Matrix::IDENTITY;
+  let mut rest = value.trim_start_matches(is_separator);
+  while !rest.is_empty() {
+    let open = rest.find('(')?;
+    let close = rest.find(')')?;
+    if close < open {
+      return None;
+    }
+    let name = rest[..open].trim();
+    let args = parse_number_list(&rest[open + 1..close])?;
+    let transform = match (name, args.as_slice()) {
+      ("matrix", &[a, b, c, d, e, f]) => Matrix { a, b, c, d, e, f },
+      ("translate", &[tx]) => Matrix::translate(tx, 0.0),
+      ("translate", &[tx, ty]) => Matrix::translate(tx, ty),
+      ("scale", &[s]) => Matrix::scale(s, s),
+      ("scale", &[sx, sy]) => Matrix::scale(sx, sy),
+      ("rotate", &[angle]) => Matrix::rotate(angle),
+      ("rotate", &[angle, cx, cy]) => Matrix::translate(cx, cy)
+        .multiply(&Matrix::rotate(angle))
+        .multiply(&Matrix::translate(-cx, -cy)),
+      ("skewX", &[angle]) => Matrix::skew_x(angle),
+      ("skewY", &[angle]) => Matrix::skew_y(angle),
+      _ => return None,
+    };
+    matrix = matrix.multiply(&transform);
+    rest = rest[close + 1..].trim_start_matches(is_separator);
+  }
+  Some(matrix)
+}
+
+fn is_separator(c: char) -> bool {
+  c == ',' || c.is_whitespace()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn assert_applies(transform: &str, point: (f64, f64), expected: (f64, f64)) {
+    let (x, y) = parse_transform(transform).unwrap().apply(point.0, point.1);
+    assert!(
+      (x - expected.0).abs() < 1e-9 && (y - expected.1).abs() < 1e-9,
+      "{}: {:?} != {:?}",
+      transform,
+      (x, y),
+      expected
+    );
+  }
+
+  #[test]
+  fn parses_every_transform_function() {
+    assert_applies("translate(10)", (1.0, 1.0), (11.0, 1.0));
+    assert_applies("translate(10,-5)", (1.0, 1.0), (11.0, -4.0));
+    assert_applies("scale(2)", (1.0, 3.0), (2.0, 6.0));
+    assert_applies("scale(2 -1)", (1.0, 3.0), (2.0, -3.0));
+    assert_applies("rotate(90)", (1.0, 0.0), (0.0, 1.0));
+    assert_applies("rotate(90 10 10)", (10.0, 0.0), (20.0, 10.0));
+    assert_applies("skewX(45)", (0.0, 10.0), (10.0, 10.0));
+    assert_applies("skewY(45)", (10.0, 0.0), (10.0, 10.0));
+    assert_eq!(
+      parse_transform("matrix(1 2 3 4 5 6)"),
+      Some(Matrix {
+        a: 1.0,
+        b: 2.0,
+        c: 3.0,
+        d: 4.0,
+        e: 5.0,
+        f: 6.0,
+      })
+    );
+  }
+
+  #[test]
+  fn applies_the_rightmost_transform_first() {
+    assert_applies("translate(10) scale(2)", (1.0, 1.0), (12.0, 2.0));
+    assert_applies("scale(2),translate(10)", (1.0, 1.0), (22.0, 2.0));
+    assert_eq!(parse_transform(""), Some(Matrix::IDENTITY));
+  }
+
+  #[test]
+  fn rejects_invalid_transforms() {
+    for value in [
+      "translate(",
+      "translate)(",
+      "foo(1)",
+      "scale(1 2 3)",
+      "rotate(a)",
+    ] {
+      assert_eq!(parse_transform(value), None, "{}", value);
+    }
+  }
+
+  #[test]
+  fn linear_part_ignores_translation() {
+    let matrix = parse_transform("translate(10 10) scale(-2 1)").unwrap();
+    assert_eq!(matrix.apply_linear(1.0, 1.0), (-2.0, 1.0));
+    assert_eq!(matrix.determinant(), -2.0);
+  }
+}
@@ -56,4 +56,7 @@ const {
   },
 })
 export default __napiModule.exports
+export const XastElement = __napiModule.exports.XastElement
+export const XastRoot = __napiModule.exports.XastRoot
+export const builtinPlugins = __napiModule.exports.builtinPlugins
 export const optimize = __napiModule.exports.optimize
@@ -108,4 +108,7 @@ const { instance: __napiInstance, module: __wasiModule, napiModule: __napiModule
   },
 })
 module.exports = __napiModule.exports
+module.exports.XastElement = __napiModule.exports.XastElement
+module.exports.XastRoot = __napiModule.exports.XastRoot
+module.exports.builtinPlugins = __napiModule.exports.builtinPlugins
 module.exports.optimize = __napiModule.exports.optimize